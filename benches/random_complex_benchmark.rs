@@ -1,15 +1,36 @@
 use criterion::{criterion_group, criterion_main, Criterion};
-use simplicial_topology::simplicial_complex::random_simplicial_complex::{generate_random_hypergraph, par_generate_random_hypergraph};
+use simplicial_topology::simplicial_complex::random_process::linial_meshulam_hitting_times;
+use simplicial_topology::simplicial_complex::random_simplicial_complex::{generate_random_hypergraph, par_generate_random_hypergraph, generate_random_simplicial_complex, Model};
 
 fn criterion_par_random_hypergraph_benchmark(c: &mut Criterion) {
     c.bench_function("parallel random hg", |b| b.iter(|| par_generate_random_hypergraph(30, vec![0.8,0.5,0.7,0.4])));
 }
 
 fn criterion_random_hypergraph_benchmark(c: &mut Criterion) {
-    c.bench_function("random hg", |b| b.iter(|| generate_random_hypergraph(&30, &vec![0.8,0.5,0.7,0.4])));
+    c.bench_function("random hg", |b| b.iter(|| generate_random_hypergraph(&30, &[0.8,0.5,0.7,0.4])));
 }
 
 
 
-criterion_group!(benches, criterion_random_hypergraph_benchmark, criterion_par_random_hypergraph_benchmark);
+fn criterion_random_clique_complex_benchmark(c: &mut Criterion) {
+    let model = Model::Clique { num_vertices: 50, prob: 0.3 };
+    c.bench_function("random clique complex", |b| b.iter(|| generate_random_simplicial_complex(&model)));
+}
+
+fn criterion_sparse_linial_meshulam_benchmark(c: &mut Criterion) {
+    let model = Model::LinialMeshulam { num_vertices: 200, dimension: 3, prob: 1.0 / 200.0 };
+    let mut group = c.benchmark_group("sparse");
+    group.sample_size(10);
+    group.bench_function("linial meshulam n=200 d=3", |b| b.iter(|| generate_random_simplicial_complex(&model)));
+    group.finish();
+}
+
+fn criterion_linial_meshulam_process_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("process");
+    group.sample_size(10);
+    group.bench_function("linial meshulam hitting times n=60 d=2", |b| b.iter(|| linial_meshulam_hitting_times(60, 2)));
+    group.finish();
+}
+
+criterion_group!(benches, criterion_random_hypergraph_benchmark, criterion_par_random_hypergraph_benchmark, criterion_random_clique_complex_benchmark, criterion_sparse_linial_meshulam_benchmark, criterion_linial_meshulam_process_benchmark);
 criterion_main!(benches);
\ No newline at end of file
@@ -1,6 +1,9 @@
 use criterion::{criterion_group, criterion_main, Criterion};
 use itertools::Itertools;
-use simplicial_topology::{simplicial_complex::{simplex::{Simplex, Facet}, simplicial_complex::SimplicialComplex, hypergraph::Hypergraph}, utils::utils::get_subvectors};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use simplicial_topology::{simplicial_complex::{simplex::{Simplex, Facet}, simplicial_complex::{SimplicialComplex, BettiAlgorithm}, hypergraph::Hypergraph}, utils::utils::get_subvectors};
+use simplicial_topology::simplicial_complex::random_simplicial_complex::{generate_random_simplicial_complex_with_rng, Model};
 
 // TODO: benchmark is minimal connected cover
 
@@ -40,5 +43,26 @@ fn criterion_betti_benchmark(c: &mut Criterion) {
     c.bench_function("betti vector", |b| b.iter(|| sc.betti_numbers()));
 }
 
-criterion_group!(benches, criterion_betti_benchmark, criterion_downward_closure_benchmark, criterion_par_downward_closure_benchmark, criterion_upward_closure_benchmark);
+fn criterion_dense_betti_benchmark(c: &mut Criterion) {
+    let simplex: Facet = Simplex::new((0..10).collect_vec());
+    let sc = SimplicialComplex::new(simplex.boundary());
+    c.bench_function("dense betti vector", |b| b.iter(|| sc.betti_numbers_dense()));
+}
+
+fn criterion_random_complex_betti_benchmark(c: &mut Criterion) {
+    let model = Model::Lower { num_vertices: 40, prob_vec: vec![1.0, 0.6, 0.7, 0.8] };
+    let sc = generate_random_simplicial_complex_with_rng(&model, &mut StdRng::seed_from_u64(25));
+    let mut group = c.benchmark_group("lower model betti vector");
+    group.sample_size(10);
+    group.bench_function("sparse", |b| b.iter(|| sc.betti_numbers_with(BettiAlgorithm::Sparse)));
+    group.bench_function("discrete morse", |b| b.iter(|| sc.betti_numbers_with(BettiAlgorithm::DiscreteMorse)));
+    group.finish();
+}
+
+fn criterion_boundary_matrix_benchmark(c: &mut Criterion) {
+    let sc = SimplicialComplex::new_from_vec(get_subvectors(&(0..16).collect_vec(), 6));
+    c.bench_function("4 dim sparse boundary matrix", |b| b.iter(|| sc.compute_sparse_k_boundary_matrix(4)));
+}
+
+criterion_group!(benches, criterion_betti_benchmark, criterion_dense_betti_benchmark, criterion_random_complex_betti_benchmark, criterion_boundary_matrix_benchmark, criterion_downward_closure_benchmark, criterion_par_downward_closure_benchmark, criterion_upward_closure_benchmark);
 criterion_main!(benches);
\ No newline at end of file
@@ -1,8 +1,11 @@
-use plotly::common::Title;
-use plotly::layout::RelayoutLayout;
+use plotly::common::{Title, Mode, ErrorData, ErrorType};
+use plotly::layout::{RelayoutLayout, Axis};
+
+use crate::simplicial_complex::parameter_sweep::ParameterSweep;
 
 use plotly::{
     Histogram,
+    Scatter,
     common::Visible,
     layout::{
         update_menu::{ButtonBuilder, UpdateMenu},
@@ -10,13 +13,13 @@ use plotly::{
     Layout, Plot
 };
 
-pub fn betti_number_histogram(betti_numbers_vec: &Vec<Vec<i32>>) -> Plot{
+pub fn betti_number_histogram(betti_numbers_vec: &[Vec<i32>]) -> Plot{
     let n = betti_numbers_vec[0].len();
     let mut plot = Plot::new();
     for k in 0..n{
         let mut tmp_betti_numbers: Vec<i32> = Vec::new();
         for betti_numbers in betti_numbers_vec.iter(){
-            tmp_betti_numbers.push(*betti_numbers.get(k).unwrap_or_else(|| &0));
+            tmp_betti_numbers.push(*betti_numbers.get(k).unwrap_or(&0));
         }
         let visible = match k {
             0 => Visible::True,
@@ -30,7 +33,7 @@ pub fn betti_number_histogram(betti_numbers_vec: &Vec<Vec<i32>>) -> Plot{
     let mut buttons = Vec::new();
     for k in 0..n{
         let k_str: &str = &k.to_string();
-        let visibility = (0..n).into_iter().map(|i| if i == k { Visible::True } else { Visible::False }).collect::<Vec<_>>();
+        let visibility = (0..n).map(|i| if i == k { Visible::True } else { Visible::False }).collect::<Vec<_>>();
         let title = "Betti number: ".to_owned() + k_str;
         let title_str = title.as_str();
         let layout = RelayoutLayout::ModifyTitle { title: Some(Title::new(title_str)) };
@@ -46,3 +49,42 @@ pub fn betti_number_histogram(betti_numbers_vec: &Vec<Vec<i32>>) -> Plot{
 
     plot
 }
+
+/// Mean Betti number against the swept parameter, one curve per dimension, with error bars giving 95% confidence
+/// intervals for the means.
+pub fn betti_mean_curves(sweep: &ParameterSweep) -> Plot{
+    let mut plot = Plot::new();
+    for k in 0..sweep.num_dimensions(){
+        let half_widths: Vec<f64> = sweep.points().iter().map(|point| {
+            let stats = point.summary.betti_statistics(k);
+            stats.confidence_interval(0.95).1 - stats.mean()
+        }).collect();
+        let trace = Scatter::new(sweep.parameters(), sweep.mean_betti_curve(k))
+            .name(format!("b_{}", k))
+            .mode(Mode::LinesMarkers)
+            .error_y(ErrorData::new(ErrorType::Data).array(half_widths));
+        plot.add_trace(trace);
+    }
+    let layout = Layout::new().title(Title::new("Mean Betti numbers"))
+        .x_axis(Axis::new().title(Title::new("parameter")))
+        .y_axis(Axis::new().title(Title::new("mean Betti number")));
+    plot.set_layout(layout);
+    plot
+}
+
+/// The probability that the reduced homology vanishes against the swept parameter, one curve per dimension. A critical
+/// parameter is where a curve crosses 1/2.
+pub fn vanishing_probability_curves(sweep: &ParameterSweep) -> Plot{
+    let mut plot = Plot::new();
+    for k in 0..sweep.num_dimensions(){
+        let trace = Scatter::new(sweep.parameters(), sweep.vanishing_probability_curve(k))
+            .name(format!("P(b_{} = 0)", k))
+            .mode(Mode::LinesMarkers);
+        plot.add_trace(trace);
+    }
+    let layout = Layout::new().title(Title::new("Probability of vanishing homology"))
+        .x_axis(Axis::new().title(Title::new("parameter")))
+        .y_axis(Axis::new().title(Title::new("probability")));
+    plot.set_layout(layout);
+    plot
+}
\ No newline at end of file
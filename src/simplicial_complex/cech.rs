@@ -0,0 +1,101 @@
+use nalgebra::{DMatrix, DVector};
+
+use crate::utils::utils::get_subvectors;
+use super::simplex::{Simplex, Facet};
+use super::simplicial_complex::SimplicialComplex;
+use super::vietoris_rips::euclidean_distance_matrix;
+
+/// The radius of the smallest Euclidean ball containing all of the points. Every subset of at most d+1 points spanning
+/// the ball's boundary is tried, taking the circumcentre within its affine hull, so this is only intended for the
+/// handful of points making up a simplex.
+pub fn minimum_enclosing_radius(points: &[Vec<f64>]) -> f64 {
+    if points.len() < 2 {
+        return 0.0
+    }
+    let distance = |x: &[f64], y: &[f64]| x.iter().zip(y.iter()).map(|(a, b)| (a - b).powi(2)).sum::<f64>().sqrt();
+    let indices: Vec<usize> = (0..points.len()).collect();
+    let max_support = points.len().min(points[0].len() + 1);
+    let mut best = f64::INFINITY;
+    for k in 2..=max_support{
+        for support in get_subvectors(&indices, k){
+            if let Some(centre) = circumcentre(&support.iter().map(|&i| points[i].clone()).collect::<Vec<Vec<f64>>>()) {
+                let radius = distance(&centre, &points[support[0]]);
+                // Allow for rounding error on the points defining the sphere
+                if radius < best && points.iter().all(|p| distance(&centre, p) <= radius * (1.0 + 1e-9) + 1e-12) {
+                    best = radius;
+                }
+            }
+        }
+    }
+    if best.is_infinite() {
+        // Only happens for repeated points, where the enclosing ball is spanned by the furthest pair
+        return get_subvectors(&indices, 2).iter().map(|e| distance(&points[e[0]], &points[e[1]])).fold(0.0, f64::max) / 2.0
+    }
+    best
+}
+
+/// The centre of the smallest sphere through all of the points, which lies in their affine hull. Returns None if the
+/// points are affinely dependent.
+fn circumcentre(points: &[Vec<f64>]) -> Option<Vec<f64>> {
+    let p0 = &points[0];
+    let differences: Vec<Vec<f64>> = points[1..].iter().map(|p| p.iter().zip(p0.iter()).map(|(a, b)| a - b).collect()).collect();
+    let dot = |x: &[f64], y: &[f64]| x.iter().zip(y.iter()).map(|(a, b)| a * b).sum::<f64>();
+    let m = differences.len();
+    let gram = DMatrix::from_fn(m, m, |i, j| 2.0 * dot(&differences[i], &differences[j]));
+    let rhs = DVector::from_fn(m, |i, _| dot(&differences[i], &differences[i]));
+    let lambda = gram.lu().solve(&rhs)?;
+    Some((0..p0.len()).map(|c| p0[c] + (0..m).map(|i| lambda[i] * differences[i][c]).sum::<f64>()).collect())
+}
+
+/// The simplices on 0..n of at most max_size vertices whose enclosing radius is at most radius, returned as the maximal
+/// ones. Candidates are cliques of the graph joining vertices at distance at most 2 * radius, which any such simplex must
+/// be, and the enclosing radius must be monotone under taking faces.
+pub(crate) fn cech_facets<T: Fn(&[usize]) -> f64>(distances: &DMatrix<f64>, radius: f64, max_size: usize, enclosing_radius: T) -> Vec<Vec<usize>> {
+    let n = distances.nrows();
+    let adjacent = |i: usize, j: usize| i != j && distances[(i, j)] <= 2.0 * radius;
+    let is_simplex = |vertices: &[usize]| enclosing_radius(vertices) <= radius;
+    let mut facets: Vec<Vec<usize>> = Vec::new();
+    let mut stack: Vec<Vec<usize>> = (0..n).map(|v| vec![v]).collect();
+    while let Some(simplex) = stack.pop() {
+        if simplex.len() < max_size {
+            let extend = |v: usize| {
+                let mut extended = simplex.clone();
+                extended.push(v);
+                extended
+            };
+            // Extensions by larger vertices are explored, any extension at all means the simplex is not maximal
+            let mut is_maximal = true;
+            for v in 0..n{
+                if simplex.contains(&v) || !simplex.iter().all(|&u| adjacent(u, v)) {
+                    continue
+                }
+                let mut extended = extend(v);
+                extended.sort();
+                if is_simplex(&extended) {
+                    is_maximal = false;
+                    if v > *simplex.last().unwrap() {
+                        stack.push(extended);
+                    }
+                }
+            }
+            if !is_maximal {
+                continue
+            }
+        }
+        facets.push(simplex);
+    }
+    facets
+}
+
+/// Čech complexes, the nerve of the closed balls of a given radius around a set of points. A set of points spans a
+/// simplex iff their balls have a common point, i.e. iff their minimum enclosing radius is at most the radius. Points
+/// are labelled by their index.
+impl SimplicialComplex {
+    pub fn cech(points: &[Vec<f64>], radius: f64, max_dim: usize) -> Self {
+        let distances = euclidean_distance_matrix(points);
+        let facets = cech_facets(&distances, radius, max_dim + 1, |vertices| {
+            minimum_enclosing_radius(&vertices.iter().map(|&v| points[v].clone()).collect::<Vec<Vec<f64>>>())
+        });
+        Self { facets: facets.into_iter().map(|f| Facet::new(f).sort()).collect() }
+    }
+}
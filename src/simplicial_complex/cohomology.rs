@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use nalgebra::DMatrix;
+
+use crate::utils::coefficients::Coefficient;
+use crate::utils::linear_algebra::{kernel_basis_over, independent_modulo, coordinates_modulo};
+use super::simplex::{Simplex, Facet};
+use super::simplicial_complex::SimplicialComplex;
+
+/// A k-cochain with coefficients in F, stored as the faces it is non-zero on together with its values there. Faces have
+/// their vertices sorted, which fixes the orientation used when evaluating cup products.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cochain<F: Coefficient> {
+    pub dimension: usize,
+    pub values: Vec<(Facet, F)>
+}
+
+impl<F: Coefficient> Cochain<F> {
+    pub fn new(dimension: usize, values: Vec<(Facet, F)>) -> Self {
+        Self { dimension, values: values.into_iter().filter(|(_, c)| !c.is_zero()).collect() }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// The value of the cochain on a k-face, zero if the face is not in its support.
+    pub fn evaluate(&self, face: &Facet) -> F {
+        self.values.iter().find(|(f, _)| f == face).map(|(_, c)| *c).unwrap_or_else(F::zero)
+    }
+}
+
+/// Cohomology of a simplicial complex, computed from the coboundary maps δ_k = B_{k+1}^T given by transposing the
+/// oriented boundary matrices.
+impl SimplicialComplex {
+    /// Returns a basis of H^k(X; F) as explicit cocycle representatives. The cocycles are the kernel of δ_k, and those
+    /// independent modulo the image of δ_{k-1} are found by reducing the coboundaries followed by the cocycles.
+    pub fn cohomology_basis_over<F: Coefficient>(&self, dim: usize) -> Vec<Cochain<F>> {
+        if self.dimension() < dim as isize {
+            return vec![]
+        }
+        let k_simplices = self.face_index(dim);
+        let k_plus_one_simplices = self.sorted_k_faces(dim+1);
+        let cocycles: Vec<Vec<F>> = kernel_basis_over::<F>(&Self::boundary_matrix_from_faces(&k_simplices, &k_plus_one_simplices).transpose());
+        let coboundaries: DMatrix<F> = match dim {
+            0 => DMatrix::from_element(k_simplices.len(), 0, F::zero()),
+            _ => Self::boundary_matrix_from_faces(&self.face_index(dim-1), k_simplices.faces()).transpose().map(F::from_i32)
+        };
+        independent_modulo(&coboundaries, &cocycles).into_iter()
+            .map(|j| {
+                let values = cocycles[j].iter().enumerate().map(|(i, &c)| (k_simplices.faces()[i].clone(), c)).collect();
+                Cochain::new(dim, values)
+            }).collect()
+    }
+
+    /// The cup product of a p-cochain and a q-cochain, the (p+q)-cochain given on a face [v_0, ..., v_{p+q}] (vertices
+    /// in increasing order) by
+    /// (α ∪ β)([v_0, ..., v_{p+q}]) = α([v_0, ..., v_p]) β([v_p, ..., v_{p+q}]).
+    pub fn cup_product<F: Coefficient>(&self, alpha: &Cochain<F>, beta: &Cochain<F>) -> Cochain<F> {
+        let (p, q) = (alpha.dimension, beta.dimension);
+        let alpha_values: HashMap<&Facet, F> = alpha.values.iter().map(|(f, c)| (f, *c)).collect();
+        let beta_values: HashMap<&Facet, F> = beta.values.iter().map(|(f, c)| (f, *c)).collect();
+        let values = self.sorted_k_faces(p + q).into_iter().filter_map(|sigma| {
+            let front: Facet = Simplex::new(sigma.vertices[..=p].to_vec());
+            let back: Facet = Simplex::new(sigma.vertices[p..].to_vec());
+            match (alpha_values.get(&front), beta_values.get(&back)) {
+                (Some(&a), Some(&b)) => Some((sigma, a * b)),
+                _ => None
+            }
+        }).collect();
+        Cochain::new(p + q, values)
+    }
+
+    /// Expresses the cohomology class of a k-cocycle in terms of a basis of H^k(X; F), as returned by
+    /// `cohomology_basis_over`. Panics if the cochain is not a cocycle.
+    pub fn cohomology_class_over<F: Coefficient>(&self, cocycle: &Cochain<F>, basis: &[Cochain<F>]) -> Vec<F> {
+        let dim = cocycle.dimension;
+        let k_simplices = self.face_index(dim);
+        let coboundaries: DMatrix<F> = match dim {
+            0 => DMatrix::from_element(k_simplices.len(), 0, F::zero()),
+            _ => Self::boundary_matrix_from_faces(&self.face_index(dim-1), k_simplices.faces()).transpose().map(F::from_i32)
+        };
+        let to_vec = |cochain: &Cochain<F>| {
+            let mut v: Vec<F> = vec![F::zero(); k_simplices.len()];
+            for (face, c) in &cochain.values{
+                v[k_simplices.index_of(face).unwrap()] = *c;
+            }
+            v
+        };
+        let basis_vecs: Vec<Vec<F>> = basis.iter().map(to_vec).collect();
+        coordinates_modulo(&coboundaries, &basis_vecs, &to_vec(cocycle)).expect("Cochain is not a cocycle in the span of the given basis.")
+    }
+
+    /// Computes the cup product H^p(X; F) x H^q(X; F) -> H^{p+q}(X; F) on bases of cohomology. Entry [i][j] holds the
+    /// coordinates of α_i ∪ β_j in the basis of H^{p+q}, where α and β run over the bases of H^p and H^q.
+    ///
+    /// This distinguishes spaces with equal betti numbers, e.g. the torus has a non-zero product of its two degree one
+    /// classes, whereas all such products vanish on S^1 ∨ S^1 ∨ S^2.
+    pub fn cup_product_table_over<F: Coefficient>(&self, p: usize, q: usize) -> Vec<Vec<Vec<F>>> {
+        let alpha_basis = self.cohomology_basis_over::<F>(p);
+        let beta_basis = if p == q { alpha_basis.clone() } else { self.cohomology_basis_over::<F>(q) };
+        let target_basis = self.cohomology_basis_over::<F>(p + q);
+        alpha_basis.iter().map(|alpha| {
+            beta_basis.iter().map(|beta| self.cohomology_class_over(&self.cup_product(alpha, beta), &target_basis)).collect()
+        }).collect()
+    }
+}
@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+
+use rand::{Rng, thread_rng};
+use rand::seq::SliceRandom;
+
+use crate::utils::utils::is_sorted_subset;
+use super::simplex::{Simplex, Facet};
+use super::simplicial_complex::SimplicialComplex;
+
+/// Degree sequences preserved by the simplicial configuration model of Young, Petri, Vaccarino and Patania.
+impl SimplicialComplex {
+    /// The sizes of the facets, in decreasing order.
+    pub fn facet_size_sequence(&self) -> Vec<usize> {
+        let mut sizes: Vec<usize> = self.facets.iter().map(|facet| facet.vertices.len()).collect();
+        sizes.sort_by(|a, b| b.cmp(a));
+        sizes
+    }
+
+    /// The number of facets containing each vertex.
+    pub fn vertex_facet_degrees(&self) -> HashMap<usize, usize> {
+        let mut degrees: HashMap<usize, usize> = HashMap::new();
+        for facet in self.facets.iter() {
+            for &v in facet.vertices.iter() {
+                *degrees.entry(v).or_insert(0) += 1;
+            }
+        }
+        degrees
+    }
+}
+
+/// A Markov chain on the complexes with the same facet sizes and vertex facet-degrees as an observed complex, the
+/// simplicial configuration model.
+///
+/// Each step picks two facets f and g and vertices u in f - g and v in g - f, and swaps them, replacing f and g by
+/// f - u + v and g - v + u. The swap is rejected if either new facet is contained in, or contains, another facet, so
+/// the facets stay maximal and the sequences are preserved. The proposal is symmetric, so the chain converges to the
+/// uniform distribution on the complexes it can reach.
+#[derive(Debug, Clone)]
+pub struct ConfigurationModelChain {
+    facets: Vec<Vec<usize>>,
+    num_steps: usize,
+    num_accepted: usize
+}
+
+impl ConfigurationModelChain {
+    /// Starts the chain at the observed complex.
+    ///
+    /// # Panics
+    ///
+    /// If a facet of the observed complex equals or is contained in another, as can happen when the facets are set
+    /// directly rather than through `SimplicialComplex::new_from_vec`.
+    pub fn new(observed: &SimplicialComplex) -> Self {
+        let facets: Vec<Vec<usize>> = observed.facets.iter().map(|facet| facet.clone().sort().vertices).collect();
+        for (i, f) in facets.iter().enumerate() {
+            assert!(facets.iter().enumerate().all(|(j, g)| i == j || !is_sorted_subset(f, g)),
+                "The facets of the observed complex must be distinct and maximal.");
+        }
+        Self { facets, num_steps: 0, num_accepted: 0 }
+    }
+
+    /// Proposes one facet rewiring and returns whether it was accepted. Checking the proposal takes time linear in
+    /// the number of facets.
+    pub fn step_with_rng<R: Rng + ?Sized>(&mut self, rng: &mut R) -> bool {
+        self.num_steps += 1;
+        if self.facets.len() < 2 {
+            return false
+        }
+        let i = rng.gen_range(0..self.facets.len());
+        let mut j = rng.gen_range(0..self.facets.len() - 1);
+        if j >= i {
+            j += 1;
+        }
+        let only_in_i: Vec<usize> = self.facets[i].iter().copied().filter(|v| self.facets[j].binary_search(v).is_err()).collect();
+        let only_in_j: Vec<usize> = self.facets[j].iter().copied().filter(|v| self.facets[i].binary_search(v).is_err()).collect();
+        // Neither facet contains the other, so both differences are non-empty
+        let (&u, &v) = (only_in_i.choose(rng).unwrap(), only_in_j.choose(rng).unwrap());
+        let new_i = swap_vertex(&self.facets[i], u, v);
+        let new_j = swap_vertex(&self.facets[j], v, u);
+        let valid = self.facets.iter().enumerate().filter(|&(k, _)| k != i && k != j).all(|(_, facet)| {
+            !is_sorted_subset(&new_i, facet) && !is_sorted_subset(facet, &new_i)
+                && !is_sorted_subset(&new_j, facet) && !is_sorted_subset(facet, &new_j)
+        }) && !is_sorted_subset(&new_i, &new_j) && !is_sorted_subset(&new_j, &new_i);
+        if valid {
+            self.facets[i] = new_i;
+            self.facets[j] = new_j;
+            self.num_accepted += 1;
+        }
+        valid
+    }
+
+    /// Runs num_steps steps and returns how many were accepted.
+    pub fn run_with_rng<R: Rng + ?Sized>(&mut self, num_steps: usize, rng: &mut R) -> usize {
+        (0..num_steps).filter(|_| self.step_with_rng(rng)).count()
+    }
+
+    pub fn run(&mut self, num_steps: usize) -> usize {
+        self.run_with_rng(num_steps, &mut thread_rng())
+    }
+
+    /// The fraction of the proposals so far that were accepted.
+    pub fn acceptance_rate(&self) -> f64 {
+        self.num_accepted as f64 / self.num_steps.max(1) as f64
+    }
+
+    /// The current state of the chain.
+    pub fn complex(&self) -> SimplicialComplex {
+        SimplicialComplex { facets: self.facets.iter().cloned().map(Facet::new).collect() }
+    }
+}
+
+fn swap_vertex(facet: &[usize], old: usize, new: usize) -> Vec<usize> {
+    let mut swapped: Vec<usize> = facet.iter().copied().filter(|&w| w != old).collect();
+    let position = swapped.binary_search(&new).unwrap_err();
+    swapped.insert(position, new);
+    swapped
+}
+
+/// A sample from the simplicial configuration model of the observed complex by rejection: the vertex stubs are shuffled
+/// and dealt into facets of the observed sizes, and the deal is kept if it gives a valid complex with no repeated vertex
+/// in a facet and no facet contained in another. This is exactly uniform but the acceptance probability can be tiny,
+/// so None is returned after max_attempts failures; `ConfigurationModelChain` is the practical alternative.
+pub fn sample_configuration_model(observed: &SimplicialComplex, max_attempts: usize) -> Option<SimplicialComplex> {
+    sample_configuration_model_with_rng(observed, max_attempts, &mut thread_rng())
+}
+
+/// As `sample_configuration_model`, drawing from the given random number generator.
+pub fn sample_configuration_model_with_rng<R: Rng + ?Sized>(observed: &SimplicialComplex, max_attempts: usize, rng: &mut R) -> Option<SimplicialComplex> {
+    let sizes = observed.facet_size_sequence();
+    let mut stubs: Vec<usize> = observed.facets.iter().flat_map(|facet| facet.vertices.iter().copied()).collect();
+    stubs.sort();
+    'attempt: for _ in 0..max_attempts {
+        stubs.shuffle(rng);
+        let mut facets: Vec<Vec<usize>> = Vec::with_capacity(sizes.len());
+        let mut start = 0;
+        for &size in sizes.iter() {
+            let mut facet = stubs[start..start + size].to_vec();
+            facet.sort();
+            if facet.windows(2).any(|w| w[0] == w[1]) {
+                continue 'attempt
+            }
+            facets.push(facet);
+            start += size;
+        }
+        // Facets are in decreasing size, so only a later facet can be contained in an earlier one
+        for (k, facet) in facets.iter().enumerate() {
+            if facets[..k].iter().any(|larger| is_sorted_subset(facet, larger)) {
+                continue 'attempt
+            }
+        }
+        return Some(SimplicialComplex { facets: facets.into_iter().map(Facet::new).collect() })
+    }
+    None
+}
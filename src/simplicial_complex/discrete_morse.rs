@@ -0,0 +1,173 @@
+use std::collections::BTreeSet;
+
+use crate::utils::linear_algebra::{SparseBinaryMatrix, sparse_rank_mod_2};
+use super::face_index::FaceIndex;
+use super::simplex::{Simplex, Facet};
+use super::simplicial_complex::SimplicialComplex;
+
+/// An acyclic matching (discrete gradient vector field) on the faces of a simplicial complex, with its critical
+/// simplices and the Morse chain complex over Z/2 they span.
+///
+/// The matching is built greedily from elementary collapses: while some face has exactly one remaining coface the two
+/// are matched and removed, and when none does a remaining face of top dimension is declared critical and removed.
+/// Matchings from collapses are acyclic, and every gradient path runs from faces removed earlier to faces removed
+/// later, which is the order the Morse boundary is computed in.
+#[derive(Debug, Clone)]
+pub struct GradientVectorField {
+    /// Faces of every dimension, with the k-faces sorted and contiguous and in increasing dimension
+    faces: Vec<Facet>,
+    dimensions: Vec<usize>,
+    boundaries: Vec<Vec<usize>>,
+    partner: Vec<Option<usize>>,
+    removal_time: Vec<usize>,
+    critical: Vec<Vec<usize>>
+}
+
+impl GradientVectorField {
+    pub fn new(complex: &SimplicialComplex) -> Self {
+        let dim = complex.dimension();
+        let face_indices: Vec<FaceIndex> = (0..=dim).map(|k| complex.face_index(k as usize)).collect();
+        let offsets: Vec<usize> = face_indices.iter().scan(0, |total, index| { let offset = *total; *total += index.len(); Some(offset) }).collect();
+        let mut faces: Vec<Facet> = Vec::new();
+        let mut dimensions: Vec<usize> = Vec::new();
+        let mut boundaries: Vec<Vec<usize>> = Vec::new();
+        for (k, index) in face_indices.iter().enumerate() {
+            for face in index.faces() {
+                let boundary = if k == 0 { Vec::new() } else {
+                    face.boundary().iter().map(|tau| offsets[k-1] + face_indices[k-1].index_of(tau).unwrap()).collect()
+                };
+                faces.push(face.clone());
+                dimensions.push(k);
+                boundaries.push(boundary);
+            }
+        }
+        let num_faces = faces.len();
+        let mut cofaces: Vec<Vec<usize>> = vec![Vec::new(); num_faces];
+        for (i, boundary) in boundaries.iter().enumerate() {
+            for &j in boundary {
+                cofaces[j].push(i);
+            }
+        }
+
+        let mut alive = vec![true; num_faces];
+        let mut alive_cofaces: Vec<usize> = cofaces.iter().map(|c| c.len()).collect();
+        let mut partner: Vec<Option<usize>> = vec![None; num_faces];
+        let mut removal_time = vec![0; num_faces];
+        let mut critical: Vec<Vec<usize>> = vec![Vec::new(); face_indices.len()];
+        let mut free: Vec<usize> = (0..num_faces).filter(|&i| alive_cofaces[i] == 1).collect();
+        let mut clock = 0;
+        // Faces are in increasing dimension, so the last alive face has top dimension and no alive cofaces
+        let mut last_alive = num_faces;
+        let mut remove = |i: usize, alive: &mut Vec<bool>, alive_cofaces: &mut Vec<usize>, free: &mut Vec<usize>| {
+            alive[i] = false;
+            removal_time[i] = clock;
+            clock += 1;
+            for &j in boundaries[i].iter() {
+                alive_cofaces[j] -= 1;
+                if alive_cofaces[j] == 1 && alive[j] {
+                    free.push(j);
+                }
+            }
+        };
+        loop {
+            if let Some(sigma) = free.pop() {
+                if !alive[sigma] || alive_cofaces[sigma] != 1 {
+                    continue
+                }
+                let tau = *cofaces[sigma].iter().find(|&&tau| alive[tau]).unwrap();
+                partner[sigma] = Some(tau);
+                partner[tau] = Some(sigma);
+                remove(tau, &mut alive, &mut alive_cofaces, &mut free);
+                remove(sigma, &mut alive, &mut alive_cofaces, &mut free);
+                continue
+            }
+            while last_alive > 0 && !alive[last_alive - 1] {
+                last_alive -= 1;
+            }
+            if last_alive == 0 {
+                break
+            }
+            let sigma = last_alive - 1;
+            critical[dimensions[sigma]].push(sigma);
+            remove(sigma, &mut alive, &mut alive_cofaces, &mut free);
+        }
+        for cells in critical.iter_mut() {
+            cells.sort();
+        }
+        Self { faces, dimensions, boundaries, partner, removal_time, critical }
+    }
+
+    /// The critical simplices of each dimension.
+    pub fn critical_simplices(&self) -> Vec<Vec<Facet>> {
+        self.critical.iter().map(|cells| cells.iter().map(|&i| self.faces[i].clone()).collect()).collect()
+    }
+
+    /// The number of critical simplices of each dimension. These bound the Betti numbers (the weak Morse
+    /// inequalities) and have the same alternating sum.
+    pub fn morse_numbers(&self) -> Vec<usize> {
+        self.critical.iter().map(|cells| cells.len()).collect()
+    }
+
+    /// The matched pairs, each a face and the coface one dimension higher it is matched to.
+    pub fn pairs(&self) -> Vec<(Facet, Facet)> {
+        (0..self.faces.len()).filter_map(|i| match self.partner[i] {
+            Some(j) if self.dimensions[j] > self.dimensions[i] => Some((self.faces[i].clone(), self.faces[j].clone())),
+            _ => None
+        }).collect()
+    }
+
+    /// The boundary of the Morse complex from dimension dim to dim-1, with rows and columns indexed by the critical
+    /// simplices in the order of `critical_simplices`.
+    ///
+    /// The boundary of a critical simplex counts the gradient paths from its faces to critical faces. Its boundary is
+    /// pushed along the matching: a face matched upwards is replaced by the rest of the boundary of its partner, a
+    /// face matched downwards ends its paths, and critical faces are kept. Faces are taken in order of removal, so
+    /// each is handled once with its final coefficient.
+    pub fn morse_boundary_matrix(&self, dim: usize) -> SparseBinaryMatrix {
+        assert!(dim >= 1 && dim < self.critical.len(), "There is no Morse boundary matrix in dimension {}.", dim);
+        let row_of = |i: usize| self.critical[dim-1].binary_search(&i).ok();
+        let columns = self.critical[dim].iter().map(|&c| {
+            let mut chain: BTreeSet<(usize, usize)> = BTreeSet::new();
+            let toggle = |chain: &mut BTreeSet<(usize, usize)>, i: usize| {
+                let key = (self.removal_time[i], i);
+                if !chain.remove(&key) {
+                    chain.insert(key);
+                }
+            };
+            for &sigma in self.boundaries[c].iter() {
+                toggle(&mut chain, sigma);
+            }
+            let mut column: Vec<usize> = Vec::new();
+            while let Some((_, sigma)) = chain.pop_first() {
+                match self.partner[sigma] {
+                    None => column.push(row_of(sigma).unwrap()),
+                    Some(tau) if self.dimensions[tau] == dim => {
+                        for &rho in self.boundaries[tau].iter().filter(|&&rho| rho != sigma) {
+                            toggle(&mut chain, rho);
+                        }
+                    },
+                    Some(_) => ()
+                }
+            }
+            column.sort();
+            column
+        }).collect();
+        SparseBinaryMatrix::new(self.critical[dim-1].len(), columns)
+    }
+
+    /// The Betti numbers over Z/2, computed from the Morse complex.
+    pub fn betti_numbers(&self) -> Vec<i32> {
+        let num_dims = self.critical.len();
+        let mut ranks: Vec<i32> = vec![0];
+        ranks.extend((1..num_dims).map(|k| sparse_rank_mod_2(self.morse_boundary_matrix(k))));
+        ranks.push(0);
+        (0..num_dims).map(|k| self.critical[k].len() as i32 - ranks[k] - ranks[k+1]).collect()
+    }
+}
+
+impl SimplicialComplex {
+    /// A discrete gradient vector field on the complex, see `GradientVectorField`.
+    pub fn gradient_vector_field(&self) -> GradientVectorField {
+        GradientVectorField::new(self)
+    }
+}
@@ -0,0 +1,104 @@
+use std::fmt;
+
+use crate::utils::statistics::{SampleStatistics, Proportion};
+use crate::utils::utils::alternating_sum;
+use super::random_simplicial_complex::{generate_many_random_betti_numbers, generate_many_random_betti_numbers_with_seed, Model};
+
+/// Monte Carlo summary of the Betti numbers of an ensemble of random simplicial complexes, as returned by
+/// `generate_many_random_betti_numbers`.
+///
+/// Samples are padded with zeros up to the largest dimension seen, so `betti_statistics(k)` counts a complex of
+/// dimension less than k as having b_k = 0.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnsembleSummary {
+    betti_numbers: Vec<Vec<i32>>
+}
+
+impl EnsembleSummary {
+    /// Panics if there are no samples.
+    pub fn new(betti_numbers: Vec<Vec<i32>>) -> Self {
+        assert!(!betti_numbers.is_empty(), "Cannot summarise an empty ensemble.");
+        let num_dims = betti_numbers.iter().map(|b| b.len()).max().unwrap();
+        let betti_numbers = betti_numbers.into_iter().map(|mut b| { b.resize(num_dims, 0); b }).collect();
+        Self { betti_numbers }
+    }
+
+    /// Samples num complexes from the model and summarises their Betti numbers.
+    pub fn from_model(num: usize, model: Model) -> Self {
+        Self::new(generate_many_random_betti_numbers(num, model))
+    }
+
+    /// As `from_model`, reproducible from the seed.
+    pub fn from_model_with_seed(num: usize, model: Model, seed: u64) -> Self {
+        Self::new(generate_many_random_betti_numbers_with_seed(num, model, seed))
+    }
+
+    pub fn num_samples(&self) -> usize {
+        self.betti_numbers.len()
+    }
+
+    /// One more than the largest dimension with a Betti number in any sample, 0 if every complex was empty.
+    pub fn num_dimensions(&self) -> usize {
+        self.betti_numbers[0].len()
+    }
+
+    /// The padded Betti numbers of every sample.
+    pub fn betti_numbers(&self) -> &[Vec<i32>] {
+        &self.betti_numbers
+    }
+
+    /// The sample of the kth Betti number.
+    pub fn betti_statistics(&self, k: usize) -> SampleStatistics {
+        SampleStatistics::from_integers(&self.kth_betti_numbers(k))
+    }
+
+    pub fn mean_betti_numbers(&self) -> Vec<f64> {
+        (0..self.num_dimensions()).map(|k| self.betti_statistics(k).mean()).collect()
+    }
+
+    pub fn betti_variances(&self) -> Vec<f64> {
+        (0..self.num_dimensions()).map(|k| self.betti_statistics(k).variance()).collect()
+    }
+
+    /// The sample of Euler characteristics, the alternating sums of the Betti numbers.
+    pub fn euler_characteristic_statistics(&self) -> SampleStatistics {
+        SampleStatistics::from_integers(&self.betti_numbers.iter().map(|b| alternating_sum(b)).collect::<Vec<i32>>())
+    }
+
+    /// The fraction of connected samples, those with b_0 = 1.
+    pub fn probability_connected(&self) -> Proportion {
+        Proportion::new(self.kth_betti_numbers(0).iter().filter(|&&b| b == 1).count(), self.num_samples())
+    }
+
+    /// The fraction of samples whose reduced kth homology vanishes, so for k = 0 this is b_0 <= 1 and otherwise
+    /// b_k = 0.
+    pub fn probability_vanishing_homology(&self, k: usize) -> Proportion {
+        let threshold = if k == 0 { 1 } else { 0 };
+        Proportion::new(self.kth_betti_numbers(k).iter().filter(|&&b| b <= threshold).count(), self.num_samples())
+    }
+
+    /// The fraction of samples whose reduced homology vanishes in every dimension (over Z/2).
+    pub fn probability_acyclic(&self) -> Proportion {
+        let acyclic = self.betti_numbers.iter().filter(|b| b.first().is_none_or(|&b_0| b_0 <= 1) && b.iter().skip(1).all(|&b_k| b_k == 0)).count();
+        Proportion::new(acyclic, self.num_samples())
+    }
+
+    fn kth_betti_numbers(&self, k: usize) -> Vec<i32> {
+        self.betti_numbers.iter().map(|b| b.get(k).copied().unwrap_or(0)).collect()
+    }
+}
+
+impl fmt::Display for EnsembleSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} samples", self.num_samples())?;
+        for k in 0..self.num_dimensions() {
+            let stats = self.betti_statistics(k);
+            let (lower, upper) = stats.confidence_interval(0.95);
+            writeln!(f, "b_{}: mean {:.4} (95% CI {:.4} to {:.4}), variance {:.4}, P(vanishing) {:.4}",
+                k, stats.mean(), lower, upper, stats.variance(), self.probability_vanishing_homology(k).estimate())?;
+        }
+        let euler = self.euler_characteristic_statistics();
+        writeln!(f, "Euler characteristic: mean {:.4}, variance {:.4}", euler.mean(), euler.variance())?;
+        write!(f, "P(connected) {:.4}", self.probability_connected().estimate())
+    }
+}
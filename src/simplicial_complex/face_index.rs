@@ -0,0 +1,41 @@
+use std::collections::HashMap;
+
+use super::simplex::Facet;
+
+/// A table of faces of a fixed dimension together with a hash map from each face to its position, so that locating
+/// a face (e.g. a row of a boundary matrix) takes constant rather than linear time. Faces are expected to have their
+/// vertices sorted, lookups of unsorted faces will miss.
+#[derive(Debug, Clone)]
+pub struct FaceIndex {
+    faces: Vec<Facet>,
+    index: HashMap<Facet, usize>
+}
+
+impl FaceIndex {
+    /// Indexes the faces in the order given.
+    pub fn new(faces: Vec<Facet>) -> Self {
+        let index: HashMap<Facet, usize> = faces.iter().cloned().enumerate().map(|(i, f)| (f, i)).collect();
+        Self { faces, index }
+    }
+
+    pub fn faces(&self) -> &[Facet] {
+        &self.faces
+    }
+
+    pub fn len(&self) -> usize {
+        self.faces.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.faces.is_empty()
+    }
+
+    /// The position of a face in the table, if present.
+    pub fn index_of(&self, face: &Facet) -> Option<usize> {
+        self.index.get(face).copied()
+    }
+
+    pub fn contains(&self, face: &Facet) -> bool {
+        self.index.contains_key(face)
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use crate::utils::linear_algebra::{SparseBinaryMatrix, sparse_column_reduction};
+use super::simplex::{Simplex, Facet};
+use super::simplicial_complex::SimplicialComplex;
+use super::face_index::FaceIndex;
+
+/// A persistence interval [birth, death), with death equal to f64::INFINITY for classes that never die.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PersistenceInterval {
+    pub birth: f64,
+    pub death: f64
+}
+
+impl PersistenceInterval {
+    pub fn new(birth: f64, death: f64) -> Self {
+        Self { birth, death }
+    }
+
+    pub fn persistence(&self) -> f64 {
+        self.death - self.birth
+    }
+
+    pub fn is_essential(&self) -> bool {
+        self.death == f64::INFINITY
+    }
+}
+
+/// A filtered simplicial complex, every simplex has a real-valued birth time and the faces of a simplex are born no
+/// later than the simplex itself. The sublevel sets are then subcomplexes, nested as the time increases.
+#[derive(Debug, Clone)]
+pub struct Filtration {
+    complex: SimplicialComplex,
+    /// All simplices, sorted by birth time and then dimension, which is a valid order to add them in
+    simplices: Vec<(Facet, f64)>
+}
+
+impl Filtration {
+    /// Builds a filtration from birth times for the simplices of a complex. Panics if a simplex of the complex has no
+    /// birth time or if a face is born after one of its cofaces.
+    pub fn new(complex: SimplicialComplex, birth_times: HashMap<Facet, f64>) -> Self {
+        let birth_times: HashMap<Facet, f64> = birth_times.into_iter().map(|(f, t)| (f.sort(), t)).collect();
+        Self::from_fn(complex, |sigma| {
+            *birth_times.get(sigma).unwrap_or_else(|| panic!("No birth time given for simplex {:?}.", sigma.vertices))
+        })
+    }
+
+    /// Builds a filtration with the birth time of each simplex given by a function, which is passed simplices with
+    /// their vertices sorted. Panics if a face is born after one of its cofaces.
+    pub fn from_fn<T: Fn(&Facet) -> f64>(complex: SimplicialComplex, birth_time: T) -> Self {
+        let mut simplices: Vec<(Facet, f64)> = Vec::new();
+        if complex.dimension() >= 0 {
+            for k in 0..(complex.dimension() as usize + 1){
+                simplices.extend(complex.sorted_k_faces(k).into_iter().map(|sigma| {
+                    let t = birth_time(&sigma);
+                    (sigma, t)
+                }));
+            }
+        }
+        let births: HashMap<&Facet, f64> = simplices.iter().map(|(f, t)| (f, *t)).collect();
+        for (sigma, t) in &simplices{
+            if sigma.dimension() < 1 {
+                continue
+            }
+            for tau in sigma.boundary(){
+                let face_time = births[&tau];
+                assert!(face_time <= *t, "Face {:?} is born at {} after its coface {:?} at {}.", tau.vertices, face_time, sigma.vertices, t);
+            }
+        }
+        simplices.sort_by(|(f, s), (g, t)| s.total_cmp(t).then(f.vertices.len().cmp(&g.vertices.len())).then(f.vertices.cmp(&g.vertices)));
+        Self { complex, simplices }
+    }
+
+    pub fn complex(&self) -> &SimplicialComplex {
+        &self.complex
+    }
+
+    /// The simplices paired with their birth times, in filtration order.
+    pub fn simplices(&self) -> &[(Facet, f64)] {
+        &self.simplices
+    }
+
+    pub fn birth_time(&self, simplex: &Facet) -> Option<f64> {
+        let sigma = simplex.clone().sort();
+        self.simplices.iter().find(|(f, _)| *f == sigma).map(|(_, t)| *t)
+    }
+
+    /// The boundary matrix over Z/2 of all simplices in filtration order, rows and columns indexed alike.
+    fn filtration_boundary_matrix(&self) -> SparseBinaryMatrix {
+        let index = FaceIndex::new(self.simplices.iter().map(|(f, _)| f.clone()).collect());
+        let columns: Vec<Vec<usize>> = self.simplices.iter().map(|(sigma, _)| {
+            if sigma.dimension() < 1 {
+                return vec![]
+            }
+            let mut column: Vec<usize> = sigma.boundary().iter().filter_map(|tau| index.index_of(tau)).collect();
+            column.sort();
+            column
+        }).collect();
+        SparseBinaryMatrix::new(self.simplices.len(), columns)
+    }
+
+    /// Computes the persistence barcodes in each dimension 0, ..., dim(X) over Z/2, by the standard column reduction of
+    /// the filtration boundary matrix. A reduced column j with lowest entry i pairs the birth of a class by simplex i
+    /// with its death at simplex j, any unpaired simplex with zero reduced column gives an essential class.
+    /// Intervals of zero length are discarded.
+    pub fn barcodes(&self) -> Vec<Vec<PersistenceInterval>> {
+        if self.complex.dimension() < 0 {
+            return vec![]
+        }
+        let reduced = sparse_column_reduction(self.filtration_boundary_matrix());
+        let mut barcodes: Vec<Vec<PersistenceInterval>> = vec![Vec::new(); self.complex.dimension() as usize + 1];
+        let mut paired: Vec<bool> = vec![false; self.simplices.len()];
+        for (j, column) in reduced.columns.iter().enumerate(){
+            if let Some(&i) = column.last() {
+                paired[i] = true;
+                paired[j] = true;
+                let (sigma, birth) = &self.simplices[i];
+                let death = self.simplices[j].1;
+                if death > *birth {
+                    barcodes[sigma.dimension() as usize].push(PersistenceInterval::new(*birth, death));
+                }
+            }
+        }
+        for (i, (sigma, birth)) in self.simplices.iter().enumerate(){
+            if !paired[i] {
+                barcodes[sigma.dimension() as usize].push(PersistenceInterval::new(*birth, f64::INFINITY));
+            }
+        }
+        barcodes
+    }
+
+    /// The persistence barcode in dimension k, see `barcodes`.
+    pub fn kth_barcode(&self, dim: usize) -> Vec<PersistenceInterval> {
+        self.barcodes().into_iter().nth(dim).unwrap_or_default()
+    }
+}
@@ -0,0 +1,36 @@
+use std::collections::HashSet;
+
+use crate::utils::utils::{maximal_cliques, get_subvectors};
+use super::simplex::{Simplex, Facet};
+use super::simplicial_complex::SimplicialComplex;
+
+/// Flag (clique) complexes of graphs, where a set of vertices is a simplex iff it is a clique.
+impl SimplicialComplex {
+    /// The flag complex of the graph with the given edges, truncated to dimension max_dim. Vertices are those appearing
+    /// in an edge.
+    pub fn flag_complex(edges: &[(usize, usize)], max_dim: usize) -> Self {
+        let mut vertices: Vec<usize> = edges.iter().flat_map(|&(u, v)| [u, v]).collect::<HashSet<usize>>().into_iter().collect();
+        vertices.sort();
+        Self::flag_complex_with_vertices(&vertices, edges, max_dim)
+    }
+
+    /// The flag complex of the graph with the given vertices and edges, truncated to dimension max_dim. Isolated
+    /// vertices are kept as vertices.
+    ///
+    /// The maximal cliques are found with Bron–Kerbosch and become the facets directly, rather than building every
+    /// clique and taking the downward closure. A maximal clique of more than max_dim+1 vertices is replaced by its
+    /// subsets of size max_dim+1.
+    pub fn flag_complex_with_vertices(vertices: &[usize], edges: &[(usize, usize)], max_dim: usize) -> Self {
+        let mut facets: HashSet<Vec<usize>> = HashSet::new();
+        for clique in maximal_cliques(vertices, edges){
+            if clique.len() > max_dim + 1 {
+                facets.extend(get_subvectors(&clique, max_dim + 1));
+            }
+            else {
+                facets.insert(clique);
+            }
+        }
+        // Distinct maximal cliques are never nested, and neither are distinct subsets of the same size
+        Self { facets: facets.into_iter().map(|f| Facet::new(f).sort()).collect() }
+    }
+}
@@ -0,0 +1,41 @@
+use std::fmt;
+
+/// A finitely generated abelian group Z^rank ⊕ Z/t_1 ⊕ ... ⊕ Z/t_m, as arises for the integral homology
+/// groups of a finite simplicial complex. The torsion coefficients satisfy t_1 | t_2 | ... | t_m and are all > 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HomologyGroup {
+    pub rank: i32,
+    pub torsion: Vec<i64>
+}
+
+impl HomologyGroup {
+    pub fn new(rank: i32, torsion: Vec<i64>) -> Self {
+        Self { rank, torsion }
+    }
+
+    /// Returns true if the group is the trivial group.
+    pub fn is_trivial(&self) -> bool {
+        self.rank == 0 && self.torsion.is_empty()
+    }
+
+    /// Returns true if the group has no torsion part.
+    pub fn is_free(&self) -> bool {
+        self.torsion.is_empty()
+    }
+}
+
+impl fmt::Display for HomologyGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut summands: Vec<String> = Vec::new();
+        match self.rank {
+            0 => (),
+            1 => summands.push("Z".to_string()),
+            r => summands.push(format!("Z^{}", r))
+        }
+        summands.extend(self.torsion.iter().map(|t| format!("Z/{}", t)));
+        if summands.is_empty() {
+            return write!(f, "0")
+        }
+        write!(f, "{}", summands.join(" + "))
+    }
+}
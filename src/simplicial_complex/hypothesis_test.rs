@@ -0,0 +1,57 @@
+use std::fmt;
+
+use crate::utils::statistics::EmpiricalTest;
+use crate::utils::utils::alternating_sum;
+use super::ensemble::EnsembleSummary;
+use super::random_simplicial_complex::Model;
+use super::simplicial_complex::SimplicialComplex;
+
+/// Tests of the Betti numbers and Euler characteristic of an observed complex against an ensemble simulated from a
+/// null model, for deciding whether its homology is unusual for the model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HypothesisTest {
+    /// One test per dimension, up to the largest dimension of the observed complex or any null sample.
+    pub betti_numbers: Vec<EmpiricalTest>,
+    pub euler_characteristic: EmpiricalTest,
+    pub null: EnsembleSummary
+}
+
+impl HypothesisTest {
+    /// Tests the observed complex against an already simulated null ensemble.
+    pub fn new(observed: &SimplicialComplex, null: EnsembleSummary) -> Self {
+        let observed_betti_numbers = observed.betti_numbers();
+        let num_dims = observed_betti_numbers.len().max(null.num_dimensions());
+        let betti_numbers = (0..num_dims).map(|k| {
+            let b_k = observed_betti_numbers.get(k).copied().unwrap_or(0) as f64;
+            EmpiricalTest::new(b_k, &null.betti_statistics(k))
+        }).collect();
+        let euler_characteristic = EmpiricalTest::new(alternating_sum(&observed_betti_numbers) as f64, &null.euler_characteristic_statistics());
+        Self { betti_numbers, euler_characteristic, null }
+    }
+
+    /// Simulates num complexes from the null model and tests the observed complex against them.
+    pub fn run(observed: &SimplicialComplex, null_model: Model, num: usize) -> Self {
+        Self::new(observed, EnsembleSummary::from_model(num, null_model))
+    }
+
+    /// As `run`, reproducible from the seed.
+    pub fn run_with_seed(observed: &SimplicialComplex, null_model: Model, num: usize, seed: u64) -> Self {
+        Self::new(observed, EnsembleSummary::from_model_with_seed(num, null_model, seed))
+    }
+
+    /// The dimensions whose observed Betti number is significant at level alpha under the two-sided test.
+    pub fn significant_dimensions(&self, alpha: f64) -> Vec<usize> {
+        (0..self.betti_numbers.len()).filter(|&k| self.betti_numbers[k].p_value_two_sided <= alpha).collect()
+    }
+}
+
+impl fmt::Display for HypothesisTest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} null samples", self.null.num_samples())?;
+        let rows = self.betti_numbers.iter().enumerate().map(|(k, test)| (format!("b_{}", k), test))
+            .chain(std::iter::once(("Euler characteristic".to_string(), &self.euler_characteristic)));
+        let lines: Vec<String> = rows.map(|(name, test)| format!("{}: observed {}, null mean {:.4}, z-score {:.4}, p-values {:.4} (greater) {:.4} (less) {:.4} (two-sided)",
+            name, test.observed, test.null_mean, test.z_score, test.p_value_greater, test.p_value_less, test.p_value_two_sided)).collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
@@ -1,4 +1,23 @@
 pub mod simplex;
 pub mod hypergraph;
+#[allow(clippy::module_inception)]
 pub mod simplicial_complex;
-pub mod random_simplicial_complex;
\ No newline at end of file
+pub mod random_simplicial_complex;
+pub mod face_index;
+pub mod homology;
+pub mod cohomology;
+pub mod relative_homology;
+pub mod filtration;
+pub mod vietoris_rips;
+pub mod flag_complex;
+pub mod cech;
+pub mod random_geometric_complex;
+pub mod random_process;
+pub mod ensemble;
+pub mod parameter_sweep;
+pub mod configuration_model;
+pub mod hypothesis_test;
+pub mod model_fitting;
+pub mod persistence_diagram;
+pub mod persistence_summaries;
+pub mod discrete_morse;
\ No newline at end of file
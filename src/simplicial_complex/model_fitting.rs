@@ -0,0 +1,140 @@
+//! Maximum likelihood fitting of random simplicial complex models to an observed complex. The Lower, Upper and
+//! Linial–Meshulam likelihoods factor into independent Bernoulli trials per dimension, so every MLE is a closed-form
+//! ratio of face counts and no numerical optimisation is needed.
+
+use super::random_simplicial_complex::Model;
+use super::simplicial_complex::SimplicialComplex;
+
+/// x log y, taken to be 0 when x = 0 so that impossible events which did not happen cost nothing.
+fn xlogy(x: f64, y: f64) -> f64 {
+    if x == 0.0 { 0.0 } else { x * y.ln() }
+}
+
+/// The log-likelihood of the given numbers of successes and failures of Bernoulli(p) trials. The counts are floats as
+/// the numbers of possible faces can overflow usize.
+fn bernoulli_log_likelihood(successes: f64, failures: f64, p: f64) -> f64 {
+    xlogy(successes, p) + xlogy(failures, 1.0 - p)
+}
+
+/// The MLE of a Bernoulli probability, 0 if there were no trials.
+fn bernoulli_estimate(successes: f64, failures: f64) -> f64 {
+    if successes + failures == 0.0 { 0.0 } else { successes / (successes + failures) }
+}
+
+/// The number C(n, size) of possible faces with the given number of vertices, as a float since it overflows usize
+/// already for C(500, 10). The running product is exact while the counts fit in the mantissa.
+fn num_possible_faces(n: usize, size: usize) -> f64 {
+    if size > n {
+        return 0.0
+    }
+    (0..size).fold(1.0, |count, i| count * (n - i) as f64 / (i + 1) as f64)
+}
+
+/// Counts the observed complex is summarised by under the Lower, Upper and LinialMeshulam models. None of them depend
+/// on the vertex labels, so the fits and likelihoods only see the complex up to relabelling.
+impl SimplicialComplex {
+    /// The number of k-faces for each k up to the dimension.
+    pub fn f_vector(&self) -> Vec<usize> {
+        if self.dimension() < 0 {
+            return Vec::new()
+        }
+        (0..=self.dimension() as usize).map(|k| self.face_index(k).len()).collect()
+    }
+
+    /// The number of facets of each dimension up to the dimension.
+    fn facet_dimension_counts(&self) -> Vec<usize> {
+        let mut counts = vec![0; (self.dimension() + 1).max(0) as usize];
+        for facet in self.facets.iter() {
+            counts[facet.vertices.len() - 1] += 1;
+        }
+        counts
+    }
+
+    /// The number of external k-faces, those not in the complex whose boundary is, for each 1 <= k <= dim. Entry 0 is
+    /// unused and 0.
+    fn external_face_counts(&self, dim: usize) -> Vec<usize> {
+        let mut counts = vec![0];
+        counts.extend((1..=dim).map(|k| self.k_external_faces(k).len()));
+        counts
+    }
+}
+
+/// The Lower model closest to the observed complex in likelihood, on its vertex set. Each k-face whose boundary is in
+/// the complex is present independently with probability p_k, so the closed form MLE is
+/// p_k = f_k / (f_k + number of external k-faces), and p_0 = 1.
+pub fn fit_lower(observed: &SimplicialComplex) -> Model {
+    let f_vector = observed.f_vector();
+    assert!(!f_vector.is_empty(), "Cannot fit a model to the empty complex.");
+    let external = observed.external_face_counts(f_vector.len() - 1);
+    let mut prob_vec = vec![1.0];
+    prob_vec.extend((1..f_vector.len()).map(|k| bernoulli_estimate(f_vector[k] as f64, external[k] as f64)));
+    Model::Lower { num_vertices: f_vector[0], prob_vec }
+}
+
+/// The Upper model closest to the observed complex in likelihood, on its vertex set. The complex is generated iff every
+/// facet is sampled and no set outside the complex is, while its other faces may or may not be. So the closed form MLE
+/// is p_k = m_k / (m_k + C(n, k+1) - f_k), with m_k the number of k-dimensional facets.
+pub fn fit_upper(observed: &SimplicialComplex) -> Model {
+    let f_vector = observed.f_vector();
+    assert!(!f_vector.is_empty(), "Cannot fit a model to the empty complex.");
+    let n = f_vector[0];
+    let facet_counts = observed.facet_dimension_counts();
+    let prob_vec = (0..f_vector.len()).map(|k| bernoulli_estimate(facet_counts[k] as f64, num_possible_faces(n, k + 1) - f_vector[k] as f64)).collect();
+    Model::Upper { num_vertices: n, prob_vec }
+}
+
+/// The LinialMeshulam model closest to the observed complex in likelihood, in the dimension of the complex, with
+/// p = f_d / C(n, d+1). Panics if the complex has dimension 0 or is missing part of its (d-1)-skeleton, as then no
+/// Linial–Meshulam complex equals it.
+pub fn fit_linial_meshulam(observed: &SimplicialComplex) -> Model {
+    let f_vector = observed.f_vector();
+    assert!(f_vector.len() >= 2, "Linial–Meshulam complexes have dimension at least 1.");
+    let (n, dimension) = (f_vector[0], f_vector.len() - 1);
+    assert!(observed.contains_full_k_skeleton(dimension - 1), "The complex does not contain its full {}-skeleton.", dimension - 1);
+    let prob = f_vector[dimension] as f64 / num_possible_faces(n, dimension + 1);
+    Model::LinialMeshulam { num_vertices: n, dimension, prob }
+}
+
+/// The log-likelihood of the observed complex (up to relabelling its vertices into 0..num_vertices) under a Lower,
+/// Upper or LinialMeshulam model, for comparing fits. Negative infinity if the model cannot produce it. Panics for other
+/// models, whose likelihoods are not available in closed form.
+pub fn log_likelihood(model: &Model, observed: &SimplicialComplex) -> f64 {
+    let f_vector = observed.f_vector();
+    let prob = |prob_vec: &[f64], k: usize| prob_vec.get(k).copied().unwrap_or(0.0);
+    match model {
+        Model::Lower { num_vertices, prob_vec } => {
+            let f_0 = f_vector.first().copied().unwrap_or(0);
+            if f_0 > *num_vertices {
+                return f64::NEG_INFINITY
+            }
+            let dim = f_vector.len().max(prob_vec.len()).saturating_sub(1).min(f_0.saturating_sub(1));
+            let external = observed.external_face_counts(dim);
+            let mut total = bernoulli_log_likelihood(f_0 as f64, (num_vertices - f_0) as f64, prob(prob_vec, 0));
+            for (k, &e_k) in external.iter().enumerate().skip(1) {
+                total += bernoulli_log_likelihood(f_vector.get(k).copied().unwrap_or(0) as f64, e_k as f64, prob(prob_vec, k));
+            }
+            total
+        },
+        Model::Upper { num_vertices, prob_vec } => {
+            if f_vector.first().copied().unwrap_or(0) > *num_vertices {
+                return f64::NEG_INFINITY
+            }
+            let facet_counts = observed.facet_dimension_counts();
+            // Beyond both vectors there are no facets and p_k = 0, so the terms vanish
+            (0..prob_vec.len().max(f_vector.len())).map(|k| {
+                let f_k = f_vector.get(k).copied().unwrap_or(0) as f64;
+                bernoulli_log_likelihood(facet_counts.get(k).copied().unwrap_or(0) as f64, num_possible_faces(*num_vertices, k + 1) - f_k, prob(prob_vec, k))
+            }).sum()
+        },
+        Model::LinialMeshulam { num_vertices, dimension, prob } => {
+            assert!(*dimension >= 1, "Linial–Meshulam complexes have dimension at least 1.");
+            // Without any d-faces the complex is its (d-1)-skeleton, of dimension d-1
+            if f_vector.len() > dimension + 1 || f_vector.first() != Some(num_vertices) || !observed.contains_full_k_skeleton(dimension - 1) {
+                return f64::NEG_INFINITY
+            }
+            let f_d = f_vector.get(*dimension).copied().unwrap_or(0) as f64;
+            bernoulli_log_likelihood(f_d, num_possible_faces(*num_vertices, dimension + 1) - f_d, *prob)
+        },
+        _ => panic!("The log-likelihood is only available for the Lower, Upper and LinialMeshulam models.")
+    }
+}
@@ -0,0 +1,125 @@
+use rand::{Rng, thread_rng};
+use rayon::prelude::*;
+
+use crate::utils::utils::derive_seed;
+pub use crate::utils::utils::linspace;
+use super::ensemble::EnsembleSummary;
+use super::random_simplicial_complex::{generate_many_random_betti_numbers_with_seed, Model};
+
+/// The parameter of a `Model` varied in a sweep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SweepParameter {
+    /// `prob` of the LinialMeshulam, Pure and Clique models.
+    Prob,
+    /// The given entry of `prob_vec` of the Lower and Upper models and their inhomogeneous versions.
+    ProbVecEntry(usize),
+    /// `radius` of the Geometric model.
+    Radius,
+    /// `num_faces` of the LinialMeshulamM and PureM models, with the value rounded to the nearest integer.
+    NumFaces
+}
+
+impl Model {
+    /// A copy of the model with the parameter set to value. Panics if the model has no such parameter.
+    pub fn with_parameter(&self, parameter: SweepParameter, value: f64) -> Model {
+        let mut model = self.clone();
+        match (&mut model, parameter) {
+            (Model::LinialMeshulam { prob, .. }, SweepParameter::Prob)
+            | (Model::Pure { prob, .. }, SweepParameter::Prob)
+            | (Model::Clique { prob, .. }, SweepParameter::Prob) => *prob = value,
+            (Model::Lower { prob_vec, .. }, SweepParameter::ProbVecEntry(k))
+            | (Model::Upper { prob_vec, .. }, SweepParameter::ProbVecEntry(k))
+            | (Model::InhomogeneousLower { prob_vec, .. }, SweepParameter::ProbVecEntry(k))
+            | (Model::InhomogeneousUpper { prob_vec, .. }, SweepParameter::ProbVecEntry(k)) => {
+                assert!(k < prob_vec.len(), "prob_vec has no entry {}.", k);
+                prob_vec[k] = value
+            },
+            (Model::Geometric { radius, .. }, SweepParameter::Radius) => *radius = value,
+            (Model::LinialMeshulamM { num_faces, .. }, SweepParameter::NumFaces)
+            | (Model::PureM { num_faces, .. }, SweepParameter::NumFaces) => {
+                assert!(value >= 0.0, "The number of faces cannot be negative.");
+                *num_faces = value.round() as usize
+            },
+            (model, parameter) => panic!("{:?} has no parameter {:?}.", model, parameter)
+        }
+        model
+    }
+}
+
+/// The ensemble at one grid point of a sweep.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepPoint {
+    pub parameter: f64,
+    pub summary: EnsembleSummary
+}
+
+/// Ensembles of a model over a grid of values of one of its parameters, for locating phase transitions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterSweep {
+    points: Vec<SweepPoint>
+}
+
+impl ParameterSweep {
+    /// Samples num complexes at each of the values of the parameter of the template model, with the grid points run in
+    /// parallel. The values are sorted into increasing order.
+    pub fn run(template: &Model, parameter: SweepParameter, values: &[f64], num: usize) -> Self {
+        Self::run_with_seed(template, parameter, values, num, thread_rng().gen())
+    }
+
+    /// As `run`, reproducible from the seed. The ith grid point (in increasing order) is sampled with seed
+    /// `derive_seed(seed, i)`.
+    pub fn run_with_seed(template: &Model, parameter: SweepParameter, values: &[f64], num: usize, seed: u64) -> Self {
+        let mut values = values.to_vec();
+        values.sort_by(|a, b| a.partial_cmp(b).expect("The parameter values contain NaN."));
+        let points = values.par_iter().enumerate().map(|(i, &value)| {
+            let model = template.with_parameter(parameter, value);
+            SweepPoint { parameter: value, summary: EnsembleSummary::new(generate_many_random_betti_numbers_with_seed(num, model, derive_seed(seed, i as u64))) }
+        }).collect();
+        Self { points }
+    }
+
+    pub fn points(&self) -> &[SweepPoint] {
+        &self.points
+    }
+
+    pub fn parameters(&self) -> Vec<f64> {
+        self.points.iter().map(|point| point.parameter).collect()
+    }
+
+    /// The largest number of Betti numbers at any grid point.
+    pub fn num_dimensions(&self) -> usize {
+        self.points.iter().map(|point| point.summary.num_dimensions()).max().unwrap_or(0)
+    }
+
+    /// The mean of b_k at each grid point.
+    pub fn mean_betti_curve(&self, k: usize) -> Vec<f64> {
+        self.points.iter().map(|point| point.summary.betti_statistics(k).mean()).collect()
+    }
+
+    /// The empirical probability that the reduced kth homology vanishes at each grid point.
+    pub fn vanishing_probability_curve(&self, k: usize) -> Vec<f64> {
+        self.points.iter().map(|point| point.summary.probability_vanishing_homology(k).estimate()).collect()
+    }
+
+    /// The estimated critical parameter for dimension k, where the probability that the reduced kth homology vanishes
+    /// first crosses 1/2. This is found by linear interpolation between the two grid points either side of the
+    /// crossing, so works whether the probability rises (homology dying) or falls (homology appearing) with the
+    /// parameter. Returns None if the curve never reaches 1/2 on the grid.
+    pub fn critical_parameter(&self, k: usize) -> Option<f64> {
+        let parameters = self.parameters();
+        let probabilities = self.vanishing_probability_curve(k);
+        for i in 0..probabilities.len() {
+            let below = probabilities[i] - 0.5;
+            if below == 0.0 {
+                return Some(parameters[i])
+            }
+            if i + 1 < probabilities.len() {
+                let above = probabilities[i+1] - 0.5;
+                if below * above < 0.0 {
+                    return Some(parameters[i] + (parameters[i+1] - parameters[i]) * below / (below - above))
+                }
+            }
+        }
+        None
+    }
+}
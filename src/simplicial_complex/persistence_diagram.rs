@@ -0,0 +1,143 @@
+use nalgebra::DMatrix;
+use rayon::prelude::*;
+
+use crate::utils::matching::{hungarian, hopcroft_karp};
+use super::filtration::{Filtration, PersistenceInterval};
+
+/// The persistence diagram of one dimension of a filtration, its barcode viewed as a multiset of points (birth, death)
+/// above the diagonal. Points with infinite death are essential classes.
+///
+/// Distances use the L-infinity distance between points, and any point may instead be matched to its nearest point on
+/// the diagonal, at distance half its persistence. Essential points can only be matched to each other, so diagrams
+/// with different numbers of them are at infinite distance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PersistenceDiagram {
+    points: Vec<PersistenceInterval>
+}
+
+impl PersistenceDiagram {
+    pub fn new(points: Vec<PersistenceInterval>) -> Self {
+        assert!(points.iter().all(|point| point.death >= point.birth), "Points of a persistence diagram must die after they are born.");
+        Self { points }
+    }
+
+    pub fn points(&self) -> &[PersistenceInterval] {
+        &self.points
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn finite_points(&self) -> Vec<PersistenceInterval> {
+        self.points.iter().copied().filter(|point| !point.is_essential()).collect()
+    }
+
+    /// The births of the essential points, in increasing order.
+    fn essential_births(&self) -> Vec<f64> {
+        let mut births: Vec<f64> = self.points.iter().filter(|point| point.is_essential()).map(|point| point.birth).collect();
+        births.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        births
+    }
+
+    /// The bottleneck distance, the least possible largest distance moved by a point in a matching of the two diagrams.
+    ///
+    /// The value is one of the pairwise and point-to-diagonal distances, so it is found exactly by binary search over
+    /// these candidates, testing each for a perfect matching with Hopcroft–Karp.
+    pub fn bottleneck_distance(&self, other: &Self) -> f64 {
+        let (births, other_births) = (self.essential_births(), other.essential_births());
+        if births.len() != other_births.len() {
+            return f64::INFINITY
+        }
+        // Sorted essential points are matched optimally in order
+        let essential = births.iter().zip(other_births.iter()).map(|(a, b)| (a - b).abs()).fold(0.0, f64::max);
+        let costs = matching_costs(&self.finite_points(), &other.finite_points(), f64::INFINITY);
+        let mut candidates: Vec<f64> = costs.iter().copied().filter(|c| c.is_finite()).collect();
+        candidates.push(0.0);
+        candidates.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        candidates.dedup();
+        let size = costs.nrows();
+        let (mut low, mut high) = (0, candidates.len() - 1);
+        while low < high {
+            let mid = (low + high) / 2;
+            let adjacency: Vec<Vec<usize>> = (0..size).map(|i| (0..size).filter(|&j| costs[(i, j)] <= candidates[mid]).collect()).collect();
+            if hopcroft_karp(size, &adjacency) == size {
+                high = mid;
+            }
+            else {
+                low = mid + 1;
+            }
+        }
+        candidates[low].max(essential)
+    }
+
+    /// The p-Wasserstein distance, the least possible p-norm of the distances moved by the points in a matching of the
+    /// two diagrams, computed exactly with the Hungarian algorithm. p must be at least 1, and p = infinity gives the
+    /// bottleneck distance.
+    pub fn wasserstein_distance(&self, other: &Self, p: f64) -> f64 {
+        assert!(p >= 1.0, "The Wasserstein distance needs p >= 1.");
+        if p.is_infinite() {
+            return self.bottleneck_distance(other)
+        }
+        let (births, other_births) = (self.essential_births(), other.essential_births());
+        if births.len() != other_births.len() {
+            return f64::INFINITY
+        }
+        let essential: f64 = births.iter().zip(other_births.iter()).map(|(a, b)| (a - b).abs().powf(p)).sum();
+        let costs = matching_costs(&self.finite_points(), &other.finite_points(), p);
+        let assignment = hungarian(&costs);
+        let finite: f64 = assignment.iter().enumerate().map(|(i, &j)| costs[(i, j)]).sum();
+        (essential + finite).powf(1.0 / p)
+    }
+}
+
+/// The cost matrix of matching the points of a to those of b, with a copy of the diagonal for each point of the other
+/// diagram. Rows are a then diagonals for b, columns are b then diagonals for a, and each entry is the distance raised
+/// to the power p (or the distance itself when p is infinite).
+fn matching_costs(a: &[PersistenceInterval], b: &[PersistenceInterval], p: f64) -> DMatrix<f64> {
+    let power = |d: f64| if p.is_infinite() { d } else { d.powf(p) };
+    let to_diagonal = |x: &PersistenceInterval| (x.death - x.birth) / 2.0;
+    let (n, m) = (a.len(), b.len());
+    DMatrix::from_fn(n + m, n + m, |i, j| {
+        match (i < n, j < m) {
+            (true, true) => power((a[i].birth - b[j].birth).abs().max((a[i].death - b[j].death).abs())),
+            // Any point of a can go to the diagonal, but only its own diagonal copy so the matrix stays small
+            (true, false) => if j - m == i { power(to_diagonal(&a[i])) } else { f64::INFINITY },
+            (false, true) => if i - n == j { power(to_diagonal(&b[j])) } else { f64::INFINITY },
+            (false, false) => 0.0
+        }
+    })
+}
+
+impl Filtration {
+    /// The persistence diagram of the kth barcode.
+    pub fn persistence_diagram(&self, dim: usize) -> PersistenceDiagram {
+        PersistenceDiagram::new(self.kth_barcode(dim))
+    }
+}
+
+/// The matrix of bottleneck distances between every pair of diagrams, with the pairs computed in parallel.
+pub fn pairwise_bottleneck_distances(diagrams: &[PersistenceDiagram]) -> DMatrix<f64> {
+    pairwise_distances(diagrams, |a, b| a.bottleneck_distance(b))
+}
+
+/// The matrix of p-Wasserstein distances between every pair of diagrams, with the pairs computed in parallel.
+pub fn pairwise_wasserstein_distances(diagrams: &[PersistenceDiagram], p: f64) -> DMatrix<f64> {
+    pairwise_distances(diagrams, |a, b| a.wasserstein_distance(b, p))
+}
+
+fn pairwise_distances(diagrams: &[PersistenceDiagram], distance: impl Fn(&PersistenceDiagram, &PersistenceDiagram) -> f64 + Sync) -> DMatrix<f64> {
+    let n = diagrams.len();
+    let pairs: Vec<(usize, usize)> = (0..n).flat_map(|i| (i+1..n).map(move |j| (i, j))).collect();
+    let values: Vec<f64> = pairs.par_iter().map(|&(i, j)| distance(&diagrams[i], &diagrams[j])).collect();
+    let mut distances = DMatrix::zeros(n, n);
+    for (&(i, j), &value) in pairs.iter().zip(values.iter()) {
+        distances[(i, j)] = value;
+        distances[(j, i)] = value;
+    }
+    distances
+}
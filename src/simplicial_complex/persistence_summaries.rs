@@ -0,0 +1,166 @@
+use nalgebra::{DMatrix, DVector};
+
+use crate::utils::statistics::normal_cdf;
+use super::filtration::Filtration;
+use super::persistence_diagram::PersistenceDiagram;
+
+/// Fixed length vectors summarising persistence diagrams, for use as features. Landscapes and images only see the
+/// finite points, as essential classes have unbounded persistence; the Betti curve counts both.
+impl PersistenceDiagram {
+    /// The number of classes alive at each time of the grid, those with birth <= t < death.
+    pub fn betti_curve(&self, grid: &[f64]) -> Vec<f64> {
+        grid.iter().map(|&t| self.points().iter().filter(|point| point.birth <= t && t < point.death).count() as f64).collect()
+    }
+
+    /// The first num_landscapes persistence landscapes of Bubenik sampled on the grid. The kth landscape at t is the
+    /// kth largest value of max(0, min(t - birth, death - t)) over the finite points.
+    pub fn landscapes(&self, num_landscapes: usize, grid: &[f64]) -> Vec<Vec<f64>> {
+        let points = self.finite_points();
+        let mut landscapes = vec![vec![0.0; grid.len()]; num_landscapes];
+        for (i, &t) in grid.iter().enumerate() {
+            let mut tents: Vec<f64> = points.iter().map(|point| (t - point.birth).min(point.death - t)).filter(|&h| h > 0.0).collect();
+            tents.sort_by(|a, b| b.partial_cmp(a).unwrap());
+            for (k, height) in tents.into_iter().take(num_landscapes).enumerate() {
+                landscapes[k][i] = height;
+            }
+        }
+        landscapes
+    }
+
+    /// The landscapes concatenated into one vector of length num_landscapes * grid.len().
+    pub fn landscape_vector(&self, num_landscapes: usize, grid: &[f64]) -> DVector<f64> {
+        DVector::from_vec(self.landscapes(num_landscapes, grid).concat())
+    }
+}
+
+/// The distribution placed at each point of a persistence image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImageKernel {
+    Gaussian { standard_deviation: f64 },
+    /// Uniform on the square of the given half-width.
+    Uniform { half_width: f64 }
+}
+
+/// The weight given to a point of a persistence image according to its persistence, which should vanish on the
+/// diagonal for the image to be stable.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Weighting {
+    Constant,
+    /// persistence / max_persistence, capped at 1.
+    Linear { max_persistence: f64 },
+    /// persistence raised to the exponent.
+    Power { exponent: f64 }
+}
+
+impl Weighting {
+    pub fn weight(&self, persistence: f64) -> f64 {
+        match self {
+            Weighting::Constant => 1.0,
+            Weighting::Linear { max_persistence } => {
+                assert!(*max_persistence > 0.0, "The maximum persistence of a linear weighting must be positive.");
+                (persistence / max_persistence).min(1.0)
+            },
+            Weighting::Power { exponent } => persistence.powf(*exponent)
+        }
+    }
+}
+
+/// The persistence images of Adams et al. Each finite point is mapped to (birth, persistence), a weighted kernel is
+/// placed there, and the resulting surface is integrated over a grid of pixels.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PersistenceImage {
+    pub birth_range: (f64, f64),
+    pub persistence_range: (f64, f64),
+    /// The number of pixels along the birth and persistence axes.
+    pub resolution: (usize, usize),
+    pub kernel: ImageKernel,
+    pub weighting: Weighting
+}
+
+impl PersistenceImage {
+    /// An image over the given ranges, with a Gaussian kernel of standard deviation one pixel width along the birth
+    /// axis and linear weighting up to the top of the persistence range.
+    pub fn new(birth_range: (f64, f64), persistence_range: (f64, f64), resolution: (usize, usize)) -> Self {
+        assert!(birth_range.0 < birth_range.1 && persistence_range.0 < persistence_range.1, "The image ranges must be non-empty.");
+        assert!(resolution.0 > 0 && resolution.1 > 0, "The image needs at least one pixel.");
+        let pixel_width = (birth_range.1 - birth_range.0) / resolution.0 as f64;
+        Self {
+            birth_range,
+            persistence_range,
+            resolution,
+            kernel: ImageKernel::Gaussian { standard_deviation: pixel_width },
+            weighting: Weighting::Linear { max_persistence: persistence_range.1 }
+        }
+    }
+
+    pub fn with_kernel(mut self, kernel: ImageKernel) -> Self {
+        let width = match kernel {
+            ImageKernel::Gaussian { standard_deviation } => standard_deviation,
+            ImageKernel::Uniform { half_width } => half_width
+        };
+        assert!(width > 0.0, "The kernel width must be positive.");
+        self.kernel = kernel;
+        self
+    }
+
+    pub fn with_weighting(mut self, weighting: Weighting) -> Self {
+        self.weighting = weighting;
+        self
+    }
+
+    /// The image of the diagram, with rows indexed by persistence and columns by birth, both increasing.
+    pub fn compute(&self, diagram: &PersistenceDiagram) -> DMatrix<f64> {
+        let birth_edges = pixel_edges(self.birth_range, self.resolution.0);
+        let persistence_edges = pixel_edges(self.persistence_range, self.resolution.1);
+        let mut image = DMatrix::zeros(self.resolution.1, self.resolution.0);
+        for point in diagram.finite_points() {
+            let persistence = point.death - point.birth;
+            let weight = self.weighting.weight(persistence);
+            // The kernel is a product, so its mass in a pixel is the product of the masses of the two intervals
+            let birth_masses = self.interval_masses(point.birth, &birth_edges);
+            let persistence_masses = self.interval_masses(persistence, &persistence_edges);
+            for (i, p_mass) in persistence_masses.iter().enumerate() {
+                for (j, b_mass) in birth_masses.iter().enumerate() {
+                    image[(i, j)] += weight * p_mass * b_mass;
+                }
+            }
+        }
+        image
+    }
+
+    /// The image flattened into a vector of length resolution.0 * resolution.1, column by column.
+    pub fn vectorise(&self, diagram: &PersistenceDiagram) -> DVector<f64> {
+        let image = self.compute(diagram);
+        DVector::from_column_slice(image.as_slice())
+    }
+
+    /// The one dimensional kernel mass centred at x in each interval between consecutive edges.
+    fn interval_masses(&self, x: f64, edges: &[f64]) -> Vec<f64> {
+        let cdf = |y: f64| match self.kernel {
+            ImageKernel::Gaussian { standard_deviation } => normal_cdf(y, x, standard_deviation),
+            ImageKernel::Uniform { half_width } => ((y - x + half_width) / (2.0 * half_width)).clamp(0.0, 1.0)
+        };
+        edges.windows(2).map(|w| cdf(w[1]) - cdf(w[0])).collect()
+    }
+}
+
+fn pixel_edges(range: (f64, f64), num_pixels: usize) -> Vec<f64> {
+    (0..=num_pixels).map(|i| range.0 + (range.1 - range.0) * i as f64 / num_pixels as f64).collect()
+}
+
+impl Filtration {
+    /// The Betti curve of every dimension on the grid, the number of classes alive at each time.
+    pub fn betti_curves(&self, grid: &[f64]) -> Vec<Vec<f64>> {
+        self.barcodes().into_iter().map(|barcode| PersistenceDiagram::new(barcode).betti_curve(grid)).collect()
+    }
+
+    /// The first num_landscapes persistence landscapes of the kth barcode, on the grid.
+    pub fn persistence_landscapes(&self, dim: usize, num_landscapes: usize, grid: &[f64]) -> Vec<Vec<f64>> {
+        self.persistence_diagram(dim).landscapes(num_landscapes, grid)
+    }
+
+    /// The persistence image of the kth barcode.
+    pub fn persistence_image(&self, dim: usize, image: &PersistenceImage) -> DMatrix<f64> {
+        image.compute(&self.persistence_diagram(dim))
+    }
+}
@@ -0,0 +1,105 @@
+use nalgebra::DMatrix;
+use rand::{Rng, thread_rng};
+
+use super::simplex::{Simplex, Facet};
+use super::simplicial_complex::SimplicialComplex;
+use super::cech::{cech_facets, minimum_enclosing_radius};
+use super::vietoris_rips::euclidean_distance_matrix;
+
+/// The space random points are sampled uniformly from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Space {
+    /// The unit cube [0,1]^d with the Euclidean metric
+    Cube,
+    /// The flat torus R^d / Z^d, i.e. the unit cube with periodic boundary
+    Torus,
+    /// The unit sphere S^d in R^{d+1}, with the Euclidean (chordal) metric of R^{d+1}
+    Sphere
+}
+
+/// The complex built on the sampled points at radius r.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeometricComplex {
+    /// The nerve of the balls of radius r
+    Cech,
+    /// The flag complex of the graph joining points at distance at most 2r, the balls of radius r pairwise intersect
+    VietorisRips
+}
+
+/// Samples points uniformly from the space, as coordinates in [0,1]^d for the cube and torus and in R^{d+1} for the
+/// sphere, where uniform points are normalised standard Gaussians.
+pub fn sample_points(num_points: usize, dimension: usize, space: Space) -> Vec<Vec<f64>> {
+    sample_points_with_rng(num_points, dimension, space, &mut thread_rng())
+}
+
+/// As `sample_points`, drawing from the given random number generator.
+pub fn sample_points_with_rng<R: Rng + ?Sized>(num_points: usize, dimension: usize, space: Space, rng: &mut R) -> Vec<Vec<f64>> {
+    match space {
+        Space::Cube | Space::Torus => (0..num_points).map(|_| (0..dimension).map(|_| rng.gen::<f64>()).collect()).collect(),
+        Space::Sphere => (0..num_points).map(|_| {
+            let gaussian: Vec<f64> = (0..dimension+1).map(|_| {
+                // Box–Muller transform, 1 - u avoids taking the log of zero
+                let (u, v): (f64, f64) = (rng.gen(), rng.gen());
+                (-2.0 * (1.0 - u).ln()).sqrt() * (2.0 * std::f64::consts::PI * v).cos()
+            }).collect();
+            let norm = gaussian.iter().map(|x| x * x).sum::<f64>().sqrt();
+            gaussian.into_iter().map(|x| x / norm).collect()
+        }).collect()
+    }
+}
+
+/// Coordinatewise the shortest displacement from y to x on the flat torus.
+fn torus_displacement(x: &[f64], y: &[f64]) -> Vec<f64> {
+    x.iter().zip(y.iter()).map(|(a, b)| {
+        let d = (a - b).rem_euclid(1.0);
+        if d > 0.5 { d - 1.0 } else { d }
+    }).collect()
+}
+
+/// The matrix of pairwise distances between points in the given space.
+pub fn distance_matrix(points: &[Vec<f64>], space: Space) -> DMatrix<f64> {
+    match space {
+        Space::Cube | Space::Sphere => euclidean_distance_matrix(points),
+        Space::Torus => DMatrix::from_fn(points.len(), points.len(), |i, j| {
+            torus_displacement(&points[i], &points[j]).iter().map(|d| d * d).sum::<f64>().sqrt()
+        })
+    }
+}
+
+/// Builds the Čech or Vietoris–Rips complex at radius r, up to dimension max_dim, on points of the given space.
+///
+/// On the torus the Čech complex is computed by lifting the points of a simplex to R^d around its first vertex, which
+/// is only valid while balls do not wrap around the torus, so r must be less than 1/4.
+pub fn geometric_complex(points: &[Vec<f64>], space: Space, complex: GeometricComplex, radius: f64, max_dim: usize) -> SimplicialComplex {
+    let distances = distance_matrix(points, space);
+    match complex {
+        GeometricComplex::VietorisRips => SimplicialComplex::vietoris_rips_from_distances(&distances, 2.0 * radius, max_dim),
+        GeometricComplex::Cech => {
+            let facets = match space {
+                Space::Cube | Space::Sphere => cech_facets(&distances, radius, max_dim + 1, |vertices| {
+                    minimum_enclosing_radius(&vertices.iter().map(|&v| points[v].clone()).collect::<Vec<Vec<f64>>>())
+                }),
+                Space::Torus => {
+                    assert!(radius < 0.25, "Čech complexes on the torus require a radius less than 1/4.");
+                    cech_facets(&distances, radius, max_dim + 1, |vertices| {
+                        let base = &points[vertices[0]];
+                        let lifts: Vec<Vec<f64>> = vertices.iter().map(|&v| torus_displacement(&points[v], base)).collect();
+                        minimum_enclosing_radius(&lifts)
+                    })
+                }
+            };
+            SimplicialComplex { facets: facets.into_iter().map(|f| Facet::new(f).sort()).collect() }
+        }
+    }
+}
+
+/// Samples num_vertices uniform points from the space of the given dimension and builds the geometric complex on them.
+pub fn generate_random_geometric_complex(num_vertices: usize, dimension: usize, radius: f64, space: Space, complex: GeometricComplex, max_dim: usize) -> SimplicialComplex {
+    generate_random_geometric_complex_with_rng(num_vertices, dimension, radius, space, complex, max_dim, &mut thread_rng())
+}
+
+/// As `generate_random_geometric_complex`, drawing from the given random number generator.
+#[allow(clippy::too_many_arguments)]
+pub fn generate_random_geometric_complex_with_rng<R: Rng + ?Sized>(num_vertices: usize, dimension: usize, radius: f64, space: Space, complex: GeometricComplex, max_dim: usize, rng: &mut R) -> SimplicialComplex {
+    geometric_complex(&sample_points_with_rng(num_vertices, dimension, space, rng), space, complex, radius, max_dim)
+}
@@ -0,0 +1,315 @@
+use std::collections::{HashMap, HashSet};
+
+use rand::{Rng, thread_rng};
+use rand::seq::SliceRandom;
+use num_integer::binomial;
+
+use crate::utils::linear_algebra::IncrementalColumnReduction;
+use crate::utils::utils::{get_subvectors, rank_combination, unrank_combination};
+use super::random_simplicial_complex::complete_skeleton_with_faces;
+use super::simplex::{Simplex, Facet};
+use super::simplicial_complex::SimplicialComplex;
+
+/// Hitting times of a random process, counted as the number of faces added. Each is the first time from which the
+/// quantity is zero for the rest of the process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HittingTimes {
+    /// The reduced homology in the tracked dimension vanishes.
+    pub homology_vanishes: usize,
+    /// There is no maximal face of the tracked dimension left, i.e. no isolated face.
+    pub no_isolated_faces: usize,
+    /// The total number of faces the process can add.
+    pub num_faces: usize
+}
+
+/// Betti numbers over Z/2 of a complex built one simplex at a time, updated as each simplex is added rather than
+/// recomputed. A new k-simplex either creates a k-cycle or kills a (k-1)-cycle, depending on whether its boundary
+/// reduces to zero against the boundaries already added.
+///
+/// Only Betti numbers up to max_dim are tracked, so simplices above dimension max_dim+1 are ignored.
+pub struct IncrementalHomology {
+    max_dim: usize,
+    index: HashMap<Vec<usize>, usize>,
+    reduction: IncrementalColumnReduction,
+    betti_numbers: Vec<i32>
+}
+
+impl IncrementalHomology {
+    pub fn new(max_dim: usize) -> Self {
+        Self { max_dim, index: HashMap::new(), reduction: IncrementalColumnReduction::new(), betti_numbers: vec![0; max_dim + 1] }
+    }
+
+    /// Adds a simplex, whose faces must all have been added already. Panics otherwise.
+    pub fn add_simplex(&mut self, simplex: &Facet) {
+        let mut vertices = simplex.vertices.clone();
+        vertices.sort();
+        let dim = vertices.len() - 1;
+        if dim > self.max_dim + 1 {
+            return
+        }
+        let mut column: Vec<usize> = get_subvectors(&vertices, dim).into_iter().filter(|face| !face.is_empty()).map(|face| {
+            match self.index.get(&face) {
+                Some(&i) => i,
+                None => panic!("The face {:?} of {:?} has not been added.", face, vertices)
+            }
+        }).collect();
+        column.sort();
+        if self.reduction.add_column(column) {
+            self.betti_numbers[dim - 1] -= 1;
+        }
+        else if dim <= self.max_dim {
+            self.betti_numbers[dim] += 1;
+        }
+        if dim <= self.max_dim {
+            let i = self.index.len();
+            self.index.insert(vertices, i);
+        }
+    }
+
+    pub fn betti_numbers(&self) -> Vec<i32> {
+        self.betti_numbers.clone()
+    }
+
+    pub fn kth_betti_number(&self, k: usize) -> i32 {
+        self.betti_numbers[k]
+    }
+
+    /// The kth reduced Betti number, which differs from the Betti number only in dimension 0 of a non-empty complex.
+    pub fn kth_reduced_betti_number(&self, k: usize) -> i32 {
+        if k == 0 && !self.index.is_empty() {
+            self.betti_numbers[0] - 1
+        }
+        else {
+            self.betti_numbers[k]
+        }
+    }
+}
+
+/// The Linial–Meshulam process: starting from the full (d-1)-skeleton on num_vertices vertices, d-faces are added one
+/// at a time. Its state after m steps of `add_random_face_with_rng` is distributed as the Linial–Meshulam model with
+/// exactly m d-faces.
+///
+/// Only the d-th boundary matrix changes, so H_{d-1} is tracked by reducing the boundary of each new face against the
+/// previous ones, with the (d-1)-faces indexed by their lexicographic rank. The full (d-1)-skeleton has reduced
+/// b_{d-1} = C(n-1, d), and each face with independent boundary lowers it by one.
+pub struct LinialMeshulamProcess {
+    num_vertices: usize,
+    dimension: usize,
+    faces: Vec<Facet>,
+    added: HashSet<Vec<usize>>,
+    reduction: IncrementalColumnReduction,
+    covered: HashSet<u128>
+}
+
+impl LinialMeshulamProcess {
+    pub fn new(num_vertices: usize, dimension: usize) -> Self {
+        assert!(dimension >= 1 && num_vertices > dimension, "The process needs dimension at least 1 and more than dimension vertices.");
+        Self { num_vertices, dimension, faces: Vec::new(), added: HashSet::new(), reduction: IncrementalColumnReduction::new(), covered: HashSet::new() }
+    }
+
+    /// Adds the given d-face. Panics if it has the wrong size, uses a vertex out of range or has already been added.
+    pub fn add_face(&mut self, face: Facet) {
+        let face = face.sort();
+        assert_eq!(face.vertices.len(), self.dimension + 1, "{:?} is not a {}-face.", face.vertices, self.dimension);
+        assert!(face.vertices.iter().all(|&v| v < self.num_vertices), "{:?} has a vertex out of range.", face.vertices);
+        assert!(self.added.insert(face.vertices.clone()), "{:?} has already been added.", face.vertices);
+        let mut column: Vec<usize> = Vec::new();
+        for boundary_face in get_subvectors(&face.vertices, self.dimension) {
+            let rank = rank_combination(&boundary_face, self.num_vertices);
+            self.covered.insert(rank);
+            column.push(rank as usize);
+        }
+        column.sort();
+        self.reduction.add_column(column);
+        self.faces.push(face);
+    }
+
+    /// Adds a d-face chosen uniformly from those not yet added, and returns it. Panics if every d-face has been added.
+    pub fn add_random_face_with_rng<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Facet {
+        let total = binomial(self.num_vertices as u128, self.dimension as u128 + 1);
+        assert!((self.faces.len() as u128) < total, "Every {}-face has already been added.", self.dimension);
+        // Rejection is cheap while the process is sparse, which is where its hitting times lie
+        loop {
+            let vertices = unrank_combination(rng.gen_range(0..total), self.num_vertices, self.dimension + 1);
+            if !self.added.contains(&vertices) {
+                let face = Facet::new(vertices);
+                self.add_face(face.clone());
+                return face
+            }
+        }
+    }
+
+    /// The d-faces added so far, in order.
+    pub fn faces(&self) -> &[Facet] {
+        &self.faces
+    }
+
+    /// The reduced (d-1)-th Betti number over Z/2 of the current complex.
+    pub fn reduced_betti_number(&self) -> usize {
+        binomial(self.num_vertices - 1, self.dimension) - self.reduction.rank()
+    }
+
+    /// The d-th Betti number over Z/2 of the current complex, the number of added faces whose boundary depends on
+    /// earlier ones.
+    pub fn top_betti_number(&self) -> usize {
+        self.faces.len() - self.reduction.rank()
+    }
+
+    /// The number of (d-1)-faces not contained in any added d-face.
+    pub fn num_isolated_faces(&self) -> usize {
+        binomial(self.num_vertices, self.dimension) - self.covered.len()
+    }
+
+    /// The current complex. This lists the whole (d-1)-skeleton, so is expensive for large processes.
+    pub fn complex(&self) -> SimplicialComplex {
+        complete_skeleton_with_faces(self.num_vertices, self.dimension, self.faces.iter().map(|face| face.vertices.clone()).collect())
+    }
+}
+
+/// The random clique complex process: starting from num_vertices isolated vertices, the edges are added one at a time
+/// and the complex is the flag complex of the current graph. Its state after m steps is distributed as the clique
+/// complex of the Erdős–Rényi graph G(n, m).
+///
+/// Adding the edge uv adds the simplices uv + S for every clique S in the common neighbourhood of u and v. These are
+/// added in increasing dimension to an `IncrementalHomology` tracking dimension `dimension`, along with the number of
+/// maximal faces of that dimension.
+pub struct CliqueProcess {
+    dimension: usize,
+    neighbours: Vec<HashSet<usize>>,
+    homology: IncrementalHomology,
+    coface_counts: HashMap<Vec<usize>, usize>,
+    num_maximal_faces: usize,
+    num_edges: usize
+}
+
+impl CliqueProcess {
+    pub fn new(num_vertices: usize, dimension: usize) -> Self {
+        let mut process = Self {
+            dimension,
+            neighbours: vec![HashSet::new(); num_vertices],
+            homology: IncrementalHomology::new(dimension),
+            coface_counts: HashMap::new(),
+            num_maximal_faces: 0,
+            num_edges: 0
+        };
+        for v in 0..num_vertices {
+            process.add_simplex(vec![v]);
+        }
+        process
+    }
+
+    /// Adds the edge uv. Panics if it is a loop or already present.
+    pub fn add_edge(&mut self, u: usize, v: usize) {
+        assert!(u != v && !self.neighbours[u].contains(&v), "({}, {}) is a loop or has already been added.", u, v);
+        let common: Vec<usize> = self.neighbours[u].intersection(&self.neighbours[v]).copied().collect();
+        self.neighbours[u].insert(v);
+        self.neighbours[v].insert(u);
+        self.num_edges += 1;
+        // Only simplices up to dimension `dimension`+1 affect the tracked quantities
+        let mut new_simplices: Vec<Vec<usize>> = Vec::new();
+        self.extend_cliques(&mut vec![u, v], &common, &mut new_simplices);
+        new_simplices.sort_by_key(|s| s.len());
+        for simplex in new_simplices {
+            self.add_simplex(simplex);
+        }
+    }
+
+    fn extend_cliques(&self, clique: &mut Vec<usize>, candidates: &[usize], simplices: &mut Vec<Vec<usize>>) {
+        simplices.push(clique.clone());
+        if clique.len() > self.dimension + 1 {
+            return
+        }
+        for (i, &w) in candidates.iter().enumerate() {
+            let remaining: Vec<usize> = candidates[i+1..].iter().copied().filter(|x| self.neighbours[w].contains(x)).collect();
+            clique.push(w);
+            self.extend_cliques(clique, &remaining, simplices);
+            clique.pop();
+        }
+    }
+
+    fn add_simplex(&mut self, mut vertices: Vec<usize>) {
+        vertices.sort();
+        let dim = vertices.len() - 1;
+        if dim == self.dimension {
+            self.coface_counts.insert(vertices.clone(), 0);
+            self.num_maximal_faces += 1;
+        }
+        else if dim == self.dimension + 1 {
+            for face in get_subvectors(&vertices, dim) {
+                let count = self.coface_counts.get_mut(&face).unwrap();
+                if *count == 0 {
+                    self.num_maximal_faces -= 1;
+                }
+                *count += 1;
+            }
+        }
+        self.homology.add_simplex(&Facet::new(vertices));
+    }
+
+    pub fn num_edges(&self) -> usize {
+        self.num_edges
+    }
+
+    /// The reduced Betti number over Z/2 in the tracked dimension.
+    pub fn reduced_betti_number(&self) -> i32 {
+        self.homology.kth_reduced_betti_number(self.dimension)
+    }
+
+    /// The number of faces of the tracked dimension not contained in a face one dimension higher.
+    pub fn num_maximal_faces(&self) -> usize {
+        self.num_maximal_faces
+    }
+}
+
+/// Runs the Linial–Meshulam process in dimension d until H_{d-1} has vanished and every (d-1)-face is covered, and
+/// returns the two hitting times. Both quantities only decrease, so the process can stop as soon as both are zero.
+pub fn linial_meshulam_hitting_times(num_vertices: usize, dimension: usize) -> HittingTimes {
+    linial_meshulam_hitting_times_with_rng(num_vertices, dimension, &mut thread_rng())
+}
+
+/// As `linial_meshulam_hitting_times`, drawing from the given random number generator.
+pub fn linial_meshulam_hitting_times_with_rng<R: Rng + ?Sized>(num_vertices: usize, dimension: usize, rng: &mut R) -> HittingTimes {
+    let mut process = LinialMeshulamProcess::new(num_vertices, dimension);
+    let mut homology_vanishes = None;
+    let mut no_isolated_faces = None;
+    loop {
+        let steps = process.faces().len();
+        if homology_vanishes.is_none() && process.reduced_betti_number() == 0 {
+            homology_vanishes = Some(steps);
+        }
+        if no_isolated_faces.is_none() && process.num_isolated_faces() == 0 {
+            no_isolated_faces = Some(steps);
+        }
+        if let (Some(homology_vanishes), Some(no_isolated_faces)) = (homology_vanishes, no_isolated_faces) {
+            return HittingTimes { homology_vanishes, no_isolated_faces, num_faces: binomial(num_vertices, dimension + 1) }
+        }
+        process.add_random_face_with_rng(rng);
+    }
+}
+
+/// Runs the clique complex process through every edge and returns the hitting times at which the reduced homology in
+/// the given dimension, and the maximal faces of that dimension, vanish for good. Neither is monotone, as both first
+/// appear and then disappear as edges are added.
+pub fn clique_hitting_times(num_vertices: usize, dimension: usize) -> HittingTimes {
+    clique_hitting_times_with_rng(num_vertices, dimension, &mut thread_rng())
+}
+
+/// As `clique_hitting_times`, drawing from the given random number generator.
+pub fn clique_hitting_times_with_rng<R: Rng + ?Sized>(num_vertices: usize, dimension: usize, rng: &mut R) -> HittingTimes {
+    // With fewer vertices the complete graph still has a maximal face of the given dimension
+    assert!(num_vertices >= dimension + 2, "The process needs at least dimension+2 vertices.");
+    let mut edges: Vec<Vec<usize>> = get_subvectors(&(0..num_vertices).collect::<Vec<usize>>(), 2);
+    edges.shuffle(rng);
+    let mut process = CliqueProcess::new(num_vertices, dimension);
+    let (mut homology_vanishes, mut no_isolated_faces) = (0, 0);
+    for (step, edge) in edges.iter().enumerate() {
+        if process.reduced_betti_number() != 0 {
+            homology_vanishes = step + 1;
+        }
+        if process.num_maximal_faces() != 0 {
+            no_isolated_faces = step + 1;
+        }
+        process.add_edge(edge[0], edge[1]);
+    }
+    HittingTimes { homology_vanishes, no_isolated_faces, num_faces: edges.len() }
+}
@@ -1,23 +1,95 @@
+use std::collections::{BTreeMap, HashSet};
+
+use itertools::Itertools;
+use num_integer::binomial;
 use rayon::prelude::*;
+use rand::{Rng, SeedableRng, thread_rng};
+use rand::rngs::StdRng;
 
-use crate::utils::utils::{randomly_select_items_from_vec, get_subvectors};
+use crate::utils::utils::{randomly_select_items_from_vec_with_rng, get_subvectors, sample_subsets_with_rng, sample_ranks_with_rng, unrank_combination,
+    sample_fixed_size_subsets_with_rng, derive_seed};
+use crate::simplicial_complex::simplex::{Simplex, Facet};
 use crate::simplicial_complex::hypergraph::Hypergraph;
+use crate::simplicial_complex::random_geometric_complex::{generate_random_geometric_complex_with_rng, Space, GeometricComplex};
 use super::simplicial_complex::SimplicialComplex;
+use super::configuration_model::ConfigurationModelChain;
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum Model {
     Lower {num_vertices: usize, prob_vec: Vec<f64>},
     Upper {num_vertices: usize, prob_vec: Vec<f64>},
     LinialMeshulam {num_vertices: usize, dimension: usize, prob: f64},
-    Pure {num_vertices: usize, dimension: usize, prob: f64, include_all_vertices: bool}
+    Pure {num_vertices: usize, dimension: usize, prob: f64, include_all_vertices: bool},
+    Clique {num_vertices: usize, prob: f64},
+    Geometric {num_vertices: usize, dimension: usize, radius: f64, space: Space, complex: GeometricComplex, max_dim: usize},
+    LinialMeshulamM {num_vertices: usize, dimension: usize, num_faces: usize},
+    PureM {num_vertices: usize, dimension: usize, num_faces: usize, include_all_vertices: bool},
+    UpperM {num_vertices: usize, num_faces_vec: Vec<usize>},
+    InhomogeneousLower {weights: Vec<f64>, prob_vec: Vec<f64>, kernel: Kernel},
+    InhomogeneousUpper {weights: Vec<f64>, prob_vec: Vec<f64>, kernel: Kernel},
+    Configuration {observed: SimplicialComplex, num_steps: usize}
+}
+
+/// How the weights of the vertices of a hyperedge combine in the inhomogeneous models. A hyperedge S of size k+1 is
+/// included with probability min(1, prob_vec[k] * kernel(weights of S)), so `Product` with edges gives Chung–Lu graphs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kernel {
+    Product,
+    Min,
+    Max,
+    Mean
+}
+
+impl Kernel {
+    pub fn evaluate(&self, weights: &[f64]) -> f64 {
+        match self {
+            Kernel::Product => weights.iter().product(),
+            Kernel::Min => weights.iter().copied().fold(f64::INFINITY, f64::min),
+            Kernel::Max => weights.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            Kernel::Mean => weights.iter().sum::<f64>() / weights.len() as f64
+        }
+    }
+
+    /// The ratio between the weights within a bucket of the inhomogeneous sampler for hyperedges of the given size. Every
+    /// kernel is increasing in each weight, and raising each weight of a hyperedge to the largest in its bucket at most
+    /// doubles the kernel.
+    fn bucket_ratio(&self, size: usize) -> f64 {
+        match self {
+            Kernel::Product => 2f64.powf(1.0 / size as f64),
+            _ => 2.0
+        }
+    }
+}
+
+/// Hyperedges of size k+1 are kept independently with probability prob_vec[k], and vertices with probability prob_vec[0].
+/// The hyperedges are sampled by geometric skipping over their lexicographic ranks, so the cost is proportional to the
+/// number of hyperedges kept rather than to C(num_vertices, k+1).
+pub fn generate_random_hypergraph(num_vertices: &usize, prob_vec: &[f64]) -> Hypergraph{
+    generate_random_hypergraph_with_rng(num_vertices, prob_vec, &mut thread_rng())
 }
 
-pub fn generate_random_hypergraph(&num_vertices: &usize, prob_vec: &Vec<f64>) -> Hypergraph{
+/// As `generate_random_hypergraph`, drawing from the given random number generator so that results can be reproduced
+/// by seeding it, e.g. with `StdRng::seed_from_u64`.
+pub fn generate_random_hypergraph_with_rng<R: Rng + ?Sized>(&num_vertices: &usize, prob_vec: &[f64], rng: &mut R) -> Hypergraph{
     let possible_vertices: Vec<usize> = (0..num_vertices).collect();
-    let vertices: Vec<usize> = randomly_select_items_from_vec(&possible_vertices, prob_vec[0]);
+    let vertices: Vec<usize> = randomly_select_items_from_vec_with_rng(&possible_vertices, prob_vec[0], rng);
+    let mut hyperedges: Vec<Vec<usize>> = Vec::new();
+    for (k, &prob) in prob_vec.iter().enumerate().skip(1){
+        hyperedges.extend(sample_subsets_with_rng(num_vertices, k+1, prob, rng));
+    }
+    Hypergraph {
+        vertices,
+        hyperedges
+    }
+}
+
+/// The uniform analogue of `generate_random_hypergraph`: exactly num_faces_vec[k] hyperedges of size k+1 (vertices for
+/// k = 0), chosen uniformly at random.
+pub fn generate_random_uniform_hypergraph_with_rng<R: Rng + ?Sized>(num_vertices: usize, num_faces_vec: &[usize], rng: &mut R) -> Hypergraph{
+    let vertices: Vec<usize> = sample_fixed_size_subsets_with_rng(num_vertices, 1, num_faces_vec[0], rng).into_iter().map(|v| v[0]).collect();
     let mut hyperedges: Vec<Vec<usize>> = Vec::new();
-    for k in 1..prob_vec.len(){
-        let k_hyperedges: Vec<Vec<usize>> = get_subvectors(&possible_vertices, k+1);
-        hyperedges.extend(randomly_select_items_from_vec(&k_hyperedges, prob_vec[k]));
+    for (k, &num_faces) in num_faces_vec.iter().enumerate().skip(1){
+        hyperedges.extend(sample_fixed_size_subsets_with_rng(num_vertices, k+1, num_faces, rng));
     }
     Hypergraph {
         vertices,
@@ -25,13 +97,98 @@ pub fn generate_random_hypergraph(&num_vertices: &usize, prob_vec: &Vec<f64>) ->
     }
 }
 
+/// The inhomogeneous analogue of `generate_random_hypergraph`, where the vertex set has a weight per vertex and a
+/// hyperedge S of size k+1 is kept with probability min(1, prob_vec[k] * kernel(weights of S)).
+pub fn generate_random_inhomogeneous_hypergraph(weights: &[f64], prob_vec: &[f64], kernel: Kernel) -> Hypergraph{
+    generate_random_inhomogeneous_hypergraph_with_rng(weights, prob_vec, kernel, &mut thread_rng())
+}
+
+/// As `generate_random_inhomogeneous_hypergraph`, drawing from the given random number generator.
+///
+/// Hyperedges are sampled by thinning within weight buckets, in the manner of Miller and Hagberg's sampler for Chung–Lu
+/// graphs. The vertices are grouped into buckets of similar weight, and for each choice of buckets the hyperedges drawn
+/// from them are first kept with the largest probability any of them can have, using geometric skipping, and each
+/// candidate then kept with the ratio of its own probability to that bound. This is exact, and as the bound is at most
+/// twice the probability of each candidate, outside of the merged lightest buckets, the number of candidates is at most
+/// about twice the number kept, however heavy-tailed the weights.
+pub fn generate_random_inhomogeneous_hypergraph_with_rng<R: Rng + ?Sized>(weights: &[f64], prob_vec: &[f64], kernel: Kernel, rng: &mut R) -> Hypergraph{
+    assert!(weights.iter().all(|&w| w >= 0.0), "Vertex weights must be non-negative.");
+    let mut sampled = prob_vec.iter().enumerate()
+        .map(|(k, &prob)| sample_inhomogeneous_subsets_with_rng(weights, k + 1, prob, kernel, rng).0)
+        .collect::<Vec<Vec<Vec<usize>>>>()
+        .into_iter();
+    Hypergraph {
+        vertices: sampled.next().unwrap_or_default().into_iter().map(|v| v[0]).collect(),
+        hyperedges: sampled.flatten().collect()
+    }
+}
+
+/// Buckets of all vertices whose weights are below the largest weight by a factor in [ratio^i, ratio^(i+1)), heaviest
+/// first. The lightest buckets are merged so that there are at most MAX_WEIGHT_BUCKETS of them, and vertices of weight
+/// zero are put in a bucket of their own.
+fn weight_buckets(weights: &[f64], ratio: f64) -> Vec<Vec<usize>> {
+    let max_weight = weights.iter().copied().fold(0.0, f64::max);
+    let mut buckets: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (v, &w) in weights.iter().enumerate(){
+        let index = match w > 0.0 {
+            true => (((max_weight / w).ln() / ratio.ln()).floor() as usize).min(MAX_WEIGHT_BUCKETS - 1),
+            false => MAX_WEIGHT_BUCKETS
+        };
+        buckets.entry(index).or_default().push(v);
+    }
+    buckets.into_values().collect()
+}
+
+const MAX_WEIGHT_BUCKETS: usize = 32;
+
+/// The hyperedges of the given size of the inhomogeneous model in lexicographic order, along with the number of
+/// candidates that were drawn before thinning.
+pub(crate) fn sample_inhomogeneous_subsets_with_rng<R: Rng + ?Sized>(weights: &[f64], size: usize, prob: f64, kernel: Kernel, rng: &mut R) -> (Vec<Vec<usize>>, usize){
+    let buckets = weight_buckets(weights, kernel.bucket_ratio(size));
+    let bucket_max_weights: Vec<f64> = buckets.iter().map(|bucket| bucket.iter().map(|&v| weights[v]).fold(0.0, f64::max)).collect();
+    let mut subsets: Vec<Vec<usize>> = Vec::new();
+    let mut num_candidates = 0;
+    for bucket_choice in (0..buckets.len()).combinations_with_replacement(size){
+        // The buckets chosen, each with the number of vertices drawn from it
+        let groups: Vec<(usize, usize)> = bucket_choice.iter().dedup_with_count().map(|(m, &b)| (b, m)).collect();
+        if groups.iter().any(|&(b, m)| m > buckets[b].len()){
+            continue
+        }
+        let bound = (prob * kernel.evaluate(&bucket_choice.iter().map(|&b| bucket_max_weights[b]).collect::<Vec<f64>>())).min(1.0);
+        let total: u128 = groups.iter().map(|&(b, m)| binomial(buckets[b].len() as u128, m as u128)).product();
+        for mut rank in sample_ranks_with_rng(total, bound, rng){
+            num_candidates += 1;
+            let mut subset: Vec<usize> = Vec::with_capacity(size);
+            for &(b, m) in &groups{
+                let count = binomial(buckets[b].len() as u128, m as u128);
+                subset.extend(unrank_combination(rank % count, buckets[b].len(), m).into_iter().map(|i| buckets[b][i]));
+                rank /= count;
+            }
+            subset.sort_unstable();
+            let p = (prob * kernel.evaluate(&subset.iter().map(|&v| weights[v]).collect::<Vec<f64>>())).min(1.0);
+            if p >= bound || rng.gen_bool(p / bound){
+                subsets.push(subset);
+            }
+        }
+    }
+    subsets.sort_unstable();
+    (subsets, num_candidates)
+}
+
 pub fn par_generate_random_hypergraph(num_vertices: usize, prob_vec: Vec<f64>) -> Hypergraph{
+    par_generate_random_hypergraph_with_seed(num_vertices, prob_vec, thread_rng().gen())
+}
+
+/// As `par_generate_random_hypergraph`, but reproducible from the seed. Each dimension is sampled with its own generator
+/// seeded from the master seed, so the result does not depend on thread scheduling.
+pub fn par_generate_random_hypergraph_with_seed(num_vertices: usize, prob_vec: Vec<f64>, seed: u64) -> Hypergraph{
     let possible_vertices: Vec<usize> = (0..num_vertices).collect();
-    let vertices: Vec<usize> = randomly_select_items_from_vec(&possible_vertices, prob_vec[0]);
+    let mut vertex_rng = StdRng::seed_from_u64(derive_seed(seed, 0));
+    let vertices: Vec<usize> = randomly_select_items_from_vec_with_rng(&possible_vertices, prob_vec[0], &mut vertex_rng);
     let hyperedges: Vec<Vec<usize>> = (1..prob_vec.len()).collect::<Vec<usize>>().par_iter().map(
         |k| {
-                        let k_hyperedges: Vec<Vec<usize>> = get_subvectors(&possible_vertices, k+1);
-                        randomly_select_items_from_vec(&k_hyperedges, prob_vec[*k])
+                        let mut rng = StdRng::seed_from_u64(derive_seed(seed, *k as u64));
+                        sample_subsets_with_rng(num_vertices, k+1, prob_vec[*k], &mut rng)
                 }
             ).collect::<Vec<Vec<Vec<usize>>>>()
             .into_iter()
@@ -43,43 +200,118 @@ pub fn par_generate_random_hypergraph(num_vertices: usize, prob_vec: Vec<f64>) -
     }
 }
 
+/// Kahle's random clique complex X(n, p), the flag complex of an Erdős–Rényi graph G(n, p). Only the edges are sampled,
+/// the facets are then the maximal cliques of the graph.
+pub fn generate_random_clique_complex(num_vertices: usize, prob: f64) -> SimplicialComplex{
+    generate_random_clique_complex_with_rng(num_vertices, prob, &mut thread_rng())
+}
+
+/// As `generate_random_clique_complex`, drawing from the given random number generator.
+pub fn generate_random_clique_complex_with_rng<R: Rng + ?Sized>(num_vertices: usize, prob: f64, rng: &mut R) -> SimplicialComplex{
+    let vertices: Vec<usize> = (0..num_vertices).collect();
+    let edges: Vec<(usize, usize)> = sample_subsets_with_rng(num_vertices, 2, prob, rng).into_iter().map(|e| (e[0], e[1])).collect();
+    SimplicialComplex::flag_complex_with_vertices(&vertices, &edges, num_vertices)
+}
+
+/// The complex made of the given faces, all of the same size, together with every subset of {0, ..., num_vertices-1} of
+/// size skeleton_size. The facets are the given faces and the skeleton subsets not contained in any of them, which avoids
+/// the quadratic maximality check of the upward closure when the skeleton is large.
+pub(crate) fn complete_skeleton_with_faces(num_vertices: usize, skeleton_size: usize, faces: Vec<Vec<usize>>) -> SimplicialComplex{
+    let covered: HashSet<Vec<usize>> = faces.iter().flat_map(|face| get_subvectors(face, skeleton_size)).collect();
+    let mut facets: Vec<Facet> = get_subvectors(&(0..num_vertices).collect::<Vec<usize>>(), skeleton_size).into_iter()
+        .filter(|subset| !covered.contains(subset))
+        .map(Facet::new)
+        .collect();
+    facets.extend(faces.into_iter().map(Facet::new));
+    SimplicialComplex { facets }
+}
+
 pub fn generate_random_simplicial_complex(model: &Model) -> SimplicialComplex{
-    let sc = match model {
+    generate_random_simplicial_complex_with_rng(model, &mut thread_rng())
+}
+
+/// As `generate_random_simplicial_complex`, drawing from the given random number generator so that results can be
+/// reproduced by seeding it.
+pub fn generate_random_simplicial_complex_with_rng<R: Rng + ?Sized>(model: &Model, rng: &mut R) -> SimplicialComplex{
+    match model {
         Model::LinialMeshulam { num_vertices, dimension, prob } => {
-            let mut prob_vec: Vec<f64> = vec![0; dimension - 1].into_iter().map(|x| x as f64).collect::<Vec<f64>>();
-            prob_vec.push(1.0);
-            prob_vec.push(prob.clone());
-            generate_random_hypergraph(num_vertices, &prob_vec).upward_closure()
+            let faces = sample_subsets_with_rng(*num_vertices, dimension + 1, *prob, rng);
+            complete_skeleton_with_faces(*num_vertices, *dimension, faces)
         },
         Model::Lower { num_vertices, prob_vec } => {
-            generate_random_hypergraph(num_vertices, prob_vec).par_downward_closure()
+            generate_random_hypergraph_with_rng(num_vertices, prob_vec, rng).par_downward_closure()
         },
         Model::Upper { num_vertices, prob_vec } => {
-            generate_random_hypergraph(num_vertices, prob_vec).upward_closure()
+            generate_random_hypergraph_with_rng(num_vertices, prob_vec, rng).upward_closure()
         },
         Model::Pure { num_vertices, dimension, prob, include_all_vertices } => {
-            let mut prob_vec: Vec<f64> = Vec::new();
+            let faces = sample_subsets_with_rng(*num_vertices, dimension + 1, *prob, rng);
+            if *include_all_vertices {
+                complete_skeleton_with_faces(*num_vertices, 1, faces)
+            }
+            else {
+                SimplicialComplex { facets: faces.into_iter().map(Facet::new).collect() }
+            }
+        },
+        Model::Clique { num_vertices, prob } => {
+            generate_random_clique_complex_with_rng(*num_vertices, *prob, rng)
+        },
+        Model::Geometric { num_vertices, dimension, radius, space, complex, max_dim } => {
+            generate_random_geometric_complex_with_rng(*num_vertices, *dimension, *radius, *space, *complex, *max_dim, rng)
+        },
+        Model::LinialMeshulamM { num_vertices, dimension, num_faces } => {
+            let faces = sample_fixed_size_subsets_with_rng(*num_vertices, dimension + 1, *num_faces, rng);
+            complete_skeleton_with_faces(*num_vertices, *dimension, faces)
+        },
+        Model::PureM { num_vertices, dimension, num_faces, include_all_vertices } => {
+            let faces = sample_fixed_size_subsets_with_rng(*num_vertices, dimension + 1, *num_faces, rng);
             if *include_all_vertices {
-                prob_vec.push(1.0)
+                complete_skeleton_with_faces(*num_vertices, 1, faces)
             }
             else {
-                prob_vec.push(0.0)
+                SimplicialComplex { facets: faces.into_iter().map(Facet::new).collect() }
             }
-            prob_vec.extend(vec![0.0; dimension - 1]);
-            prob_vec.push(prob.clone());
-            generate_random_hypergraph(num_vertices, &prob_vec).upward_closure()
+        },
+        Model::UpperM { num_vertices, num_faces_vec } => {
+            generate_random_uniform_hypergraph_with_rng(*num_vertices, num_faces_vec, rng).upward_closure()
+        },
+        Model::InhomogeneousLower { weights, prob_vec, kernel } => {
+            generate_random_inhomogeneous_hypergraph_with_rng(weights, prob_vec, *kernel, rng).par_downward_closure()
+        },
+        Model::InhomogeneousUpper { weights, prob_vec, kernel } => {
+            generate_random_inhomogeneous_hypergraph_with_rng(weights, prob_vec, *kernel, rng).upward_closure()
+        },
+        Model::Configuration { observed, num_steps } => {
+            let mut chain = ConfigurationModelChain::new(observed);
+            chain.run_with_rng(*num_steps, rng);
+            chain.complex()
         }
 
-    };
-    sc
+    }
 }
 
 pub fn generate_many_random_simplicial_complexes(num: usize, model: Model) -> Vec<SimplicialComplex>{
-    (0..num).collect::<Vec<usize>>().into_par_iter().map(|_| generate_random_simplicial_complex(&model)).collect()
+    generate_many_random_simplicial_complexes_with_seed(num, model, thread_rng().gen())
+}
+
+/// As `generate_many_random_simplicial_complexes`, but reproducible from the seed. The ith complex is sampled with its
+/// own generator seeded by `derive_seed(seed, i)`, so the output does not depend on thread scheduling and any single
+/// sample can be regenerated with `generate_random_simplicial_complex_with_rng`.
+pub fn generate_many_random_simplicial_complexes_with_seed(num: usize, model: Model, seed: u64) -> Vec<SimplicialComplex>{
+    (0..num).collect::<Vec<usize>>().into_par_iter().map(|i| {
+        let mut rng = StdRng::seed_from_u64(derive_seed(seed, i as u64));
+        generate_random_simplicial_complex_with_rng(&model, &mut rng)
+    }).collect()
 }
 
 pub fn generate_many_random_betti_numbers(num: usize, model: Model) -> Vec<Vec<i32>>{
-    let complexes = generate_many_random_simplicial_complexes(num, model);
+    generate_many_random_betti_numbers_with_seed(num, model, thread_rng().gen())
+}
+
+/// As `generate_many_random_betti_numbers`, but reproducible from the seed, see
+/// `generate_many_random_simplicial_complexes_with_seed`.
+pub fn generate_many_random_betti_numbers_with_seed(num: usize, model: Model, seed: u64) -> Vec<Vec<i32>>{
+    let complexes = generate_many_random_simplicial_complexes_with_seed(num, model, seed);
     let betti_numbers: Vec<Vec<i32>> = complexes.into_par_iter().map(|sc| sc.betti_numbers()).collect();
     betti_numbers
 }
\ No newline at end of file
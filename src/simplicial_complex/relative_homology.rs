@@ -0,0 +1,146 @@
+use nalgebra::DMatrix;
+
+use crate::utils::coefficients::{Coefficient, Fp};
+use crate::utils::linear_algebra::{rank_over, row_echelon_form, kernel_basis_over, independent_modulo, coordinates_modulo};
+use super::simplex::{Simplex, Facet};
+use super::simplicial_complex::SimplicialComplex;
+use super::face_index::FaceIndex;
+
+/// The connecting homomorphism H_k(X, A) -> H_{k-1}(A) of the long exact sequence of a pair, written in terms of
+/// explicit bases of the two homology groups.
+#[derive(Debug, Clone)]
+pub struct ConnectingMap<F: Coefficient> {
+    pub dimension: usize,
+    /// Relative cycles representing a basis of H_k(X, A)
+    pub domain_basis: Vec<Vec<(Facet, F)>>,
+    /// Cycles of A representing a basis of H_{k-1}(A)
+    pub codomain_basis: Vec<Vec<(Facet, F)>>,
+    /// Column j holds the coordinates of the image of domain_basis[j] in terms of codomain_basis
+    pub matrix: DMatrix<F>
+}
+
+impl<F: Coefficient> ConnectingMap<F> {
+    /// The rank of the connecting map over F.
+    pub fn rank(&self) -> i32 {
+        let mut m = self.matrix.clone();
+        row_echelon_form(&mut m, false).len() as i32
+    }
+}
+
+/// Homology of a pair (X, A), where A is a subcomplex of X, computed from the quotient chain complex
+/// C_k(X, A) = C_k(X) / C_k(A), which has a basis given by the k-faces of X that are not in A.
+impl SimplicialComplex {
+    fn relative_k_faces(&self, sub: &SimplicialComplex, dim: usize) -> Vec<Facet> {
+        let sub_faces = sub.face_index(dim);
+        self.sorted_k_faces(dim).into_iter().filter(|f| !sub_faces.contains(f)).collect()
+    }
+
+    fn assert_subcomplex(&self, sub: &SimplicialComplex) {
+        assert!(self.contains(sub), "The subcomplex is not contained in the simplicial complex.");
+    }
+
+    /// Computes the relative betti numbers b_k(X, A) over the coefficient field F for k = 0, ..., dim(X).
+    ///
+    /// # Panics
+    ///
+    /// If `sub` is not a subcomplex of the complex.
+    pub fn relative_betti_numbers_over<F: Coefficient>(&self, sub: &SimplicialComplex) -> Vec<i32> {
+        self.assert_subcomplex(sub);
+        if self.dimension() < 0 {
+            return vec![]
+        }
+        let dim = self.dimension() as usize;
+        let faces: Vec<FaceIndex> = (0..(dim+1)).map(|k| FaceIndex::new(self.relative_k_faces(sub, k))).collect();
+        // ranks[k] is the rank of the relative boundary map from k-chains, with the map from 0-chains being zero
+        let mut ranks: Vec<i32> = vec![0];
+        ranks.extend((1..(dim+1)).map(|k| rank_over::<F>(&Self::boundary_matrix_from_faces(&faces[k-1], faces[k].faces()))));
+        ranks.push(0);
+        (0..(dim+1)).map(|k| faces[k].len() as i32 - ranks[k] - ranks[k+1]).collect()
+    }
+
+    /// Computes the relative betti numbers b_k(X, A) over Z/2, agreeing with `betti_numbers` when A is empty.
+    ///
+    /// # Panics
+    ///
+    /// If `sub` is not a subcomplex of the complex.
+    pub fn relative_betti_numbers(&self, sub: &SimplicialComplex) -> Vec<i32> {
+        self.relative_betti_numbers_over::<Fp<2>>(sub)
+    }
+
+    /// Returns a basis of H_k(X, A; F) as relative cycles, chains on the k-faces not in A whose boundary lies in A.
+    ///
+    /// # Panics
+    ///
+    /// If `sub` is not a subcomplex of the complex.
+    pub fn relative_homology_basis_over<F: Coefficient>(&self, sub: &SimplicialComplex, dim: usize) -> Vec<Vec<(Facet, F)>> {
+        self.assert_subcomplex(sub);
+        if self.dimension() < dim as isize {
+            return vec![]
+        }
+        let k_simplices = FaceIndex::new(self.relative_k_faces(sub, dim));
+        let cycles: Vec<Vec<F>> = match dim {
+            0 => (0..k_simplices.len()).map(|i| (0..k_simplices.len()).map(|j| if i == j { F::one() } else { F::zero() }).collect()).collect(),
+            _ => kernel_basis_over::<F>(&Self::boundary_matrix_from_faces(&FaceIndex::new(self.relative_k_faces(sub, dim-1)), k_simplices.faces()))
+        };
+        let boundaries: DMatrix<F> = Self::boundary_matrix_from_faces(&k_simplices, &self.relative_k_faces(sub, dim+1)).map(F::from_i32);
+        independent_modulo(&boundaries, &cycles).into_iter()
+            .map(|j| {
+                cycles[j].iter().enumerate()
+                    .filter(|(_, c)| !c.is_zero())
+                    .map(|(i, &c)| (k_simplices.faces()[i].clone(), c))
+                    .collect()
+            }).collect()
+    }
+
+    /// Computes the connecting homomorphism H_k(X, A; F) -> H_{k-1}(A; F) for k >= 1, which sends a relative cycle to the
+    /// homology class of its boundary in A.
+    ///
+    /// # Panics
+    ///
+    /// If `sub` is not a subcomplex of the complex, or if dim is 0.
+    pub fn connecting_map_over<F: Coefficient>(&self, sub: &SimplicialComplex, dim: usize) -> ConnectingMap<F> {
+        self.assert_subcomplex(sub);
+        assert!(dim >= 1, "The connecting map is only defined from H_k(X, A) for k >= 1.");
+        let domain_basis = self.relative_homology_basis_over::<F>(sub, dim);
+        let codomain_basis = sub.homology_basis_over::<F>(dim-1);
+        let sub_faces = sub.face_index(dim-1);
+        let to_vec = |chain: &Vec<(Facet, F)>| {
+            let mut v: Vec<F> = vec![F::zero(); sub_faces.len()];
+            // Faces outside of A are skipped, on the boundary of a relative cycle their coefficients sum to zero
+            for (face, c) in chain{
+                if let Some(i) = sub_faces.index_of(face) {
+                    v[i] = v[i] + *c;
+                }
+            }
+            v
+        };
+        let sub_boundaries: DMatrix<F> = Self::boundary_matrix_from_faces(&sub_faces, &sub.sorted_k_faces(dim)).map(F::from_i32);
+        let codomain_vecs: Vec<Vec<F>> = codomain_basis.iter().map(to_vec).collect();
+        let mut matrix: DMatrix<F> = DMatrix::from_element(codomain_basis.len(), domain_basis.len(), F::zero());
+        for (j, relative_cycle) in domain_basis.iter().enumerate(){
+            let boundary: Vec<(Facet, F)> = relative_cycle.iter().flat_map(|(face, c)| {
+                face.boundary().into_iter().enumerate().map(move |(i, tau)| (tau, F::from_i32((-1i32).pow(i as u32)) * *c))
+            }).collect();
+            let coordinates = coordinates_modulo(&sub_boundaries, &codomain_vecs, &to_vec(&boundary))
+                .expect("The boundary of a relative cycle should be a cycle in the subcomplex.");
+            for (i, c) in coordinates.into_iter().enumerate(){
+                matrix[(i, j)] = c;
+            }
+        }
+        ConnectingMap { dimension: dim, domain_basis, codomain_basis, matrix }
+    }
+
+    /// Computes all connecting homomorphisms H_k(X, A; F) -> H_{k-1}(A; F) of the long exact sequence of the pair,
+    /// for k = 1, ..., dim(X).
+    ///
+    /// # Panics
+    ///
+    /// If `sub` is not a subcomplex of the complex.
+    pub fn connecting_maps_over<F: Coefficient>(&self, sub: &SimplicialComplex) -> Vec<ConnectingMap<F>> {
+        self.assert_subcomplex(sub);
+        if self.dimension() < 1 {
+            return vec![]
+        }
+        (1..(self.dimension() as usize + 1)).map(|k| self.connecting_map_over(sub, k)).collect()
+    }
+}
@@ -11,7 +11,7 @@ pub trait Simplex {
     fn sort(self) -> Self;
     fn has_subface(&self, simplex: &Facet) -> bool;
     fn link(self, simplex: &Facet) -> Self;
-    fn dual (&self, vertex_set: &Vec<usize>) -> Self;
+    fn dual (&self, vertex_set: &[usize]) -> Self;
     fn print(&self);
 }
 
@@ -109,7 +109,7 @@ impl Simplex for Facet{
         Self { vertices:  self.vertices.iter().filter(|&elem| !simplex.vertices.contains(elem)).cloned().collect()}
     }
 
-    fn dual(&self, vertex_set: &Vec<usize>) -> Self{
+    fn dual(&self, vertex_set: &[usize]) -> Self{
         let vertices = vertex_set.iter().filter(|v| !self.vertices.contains(v)).cloned().collect();
         Self { vertices }
     }
@@ -5,10 +5,26 @@ use num_integer::binomial;
 use rayon::prelude::*;
 
 use crate::utils::utils::{alternating_sum, filter_maximal_sets, remove_element};
-use crate::utils::linear_algebra::{rank_smith_normal_matrix, row_nullity_smith_normal_matrix, gaussian_elimination};
+use crate::utils::linear_algebra::{rank_smith_normal_matrix, row_nullity_smith_normal_matrix, gaussian_elimination, integer_smith_normal_form, rank_over, kernel_basis_over, independent_modulo, SparseBinaryMatrix, sparse_rank_mod_2};
+use crate::utils::coefficients::{Coefficient, Fp};
 use crate::simplicial_complex::simplex::{Simplex, Facet};
 
 use super::simplex::{simplex_intersection, simplex_join};
+use super::homology::HomologyGroup;
+use super::face_index::FaceIndex;
+
+/// How `betti_numbers_with` computes the ranks of the boundary maps over Z/2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BettiAlgorithm {
+    /// Sparse column reduction of the boundary matrices
+    #[default]
+    Sparse,
+    /// Gaussian elimination on dense boundary matrices
+    Dense,
+    /// Sparse column reduction of the much smaller boundary matrices of the Morse complex of a discrete gradient, see
+    /// `GradientVectorField`
+    DiscreteMorse
+}
 
 #[derive(Debug)]
 pub struct SimplicialComplex {
@@ -20,7 +36,7 @@ impl PartialEq for SimplicialComplex {
         if self.facets.len() != other.facets.len() {
             return false;
         }
-        for (_, facet) in self.facets.iter().enumerate() {
+        for facet in self.facets.iter() {
             if !other.facets.contains(facet){
                 return false;
             }
@@ -43,7 +59,7 @@ impl SimplicialComplex {
     }
 
     pub fn new_from_vec(v: Vec<Vec<usize>>) -> Self{
-        let facets: Vec<Facet> = filter_maximal_sets(v).into_iter().map(|x| Simplex::new(x)).collect();
+        let facets: Vec<Facet> = filter_maximal_sets(v).into_iter().map(Simplex::new).collect();
         Self { facets: facets.into_iter().map(|facet| facet.sort()).collect() }
     }
 
@@ -64,7 +80,7 @@ impl SimplicialComplex {
     pub fn union(&self, sc: &Self) -> Self{
         let mut facets = self.facets.clone();
         facets.append(&mut sc.clone().facets);
-        return Self::new(facets)
+        Self::new(facets)
     }
 
     /// Intersection of two complexes, returns a new complex
@@ -101,8 +117,8 @@ impl SimplicialComplex {
     }
 
     pub fn k_external_faces(&self, dim: usize) -> Vec<Facet>{
-        let k_minus_one_simplices = self.k_faces(dim-1);
-        let k_simplices = self.k_faces(dim);
+        let k_minus_one_simplices = self.face_index(dim-1);
+        let k_simplices = self.face_index(dim);
         let vertices: Vec<usize> = self.k_faces(0).iter().map(|v| v.vertices[0]).collect();
         let mut external_simplices = Self::new_from_vec(vec![vertices]).k_faces(dim);
         external_simplices.retain(|sigma| !k_simplices.contains(sigma));
@@ -111,7 +127,7 @@ impl SimplicialComplex {
     }
 
     fn external_faces(&self) -> Vec<Facet>{
-        if &self.dimension() < &1{
+        if self.dimension() < 1{
             return Vec::new(); 
         }
         let dim: usize = self.dimension() as usize;
@@ -198,6 +214,19 @@ impl SimplicialComplex {
         k_faces_set.into_iter().collect()
     }
 
+    /// The k-faces with sorted vertices, in lexicographic order. Unlike `k_faces` the order is deterministic, so bases
+    /// built on top of it do not change between calls.
+    pub(crate) fn sorted_k_faces(&self, dim: usize) -> Vec<Facet> {
+        let mut faces: Vec<Facet> = self.k_faces(dim).into_iter().map(|f| f.sort()).collect();
+        faces.sort_by(|a, b| a.vertices.cmp(&b.vertices));
+        faces
+    }
+
+    /// Returns a `FaceIndex` of the k-faces, in the same order as `sorted_k_faces`, for constant time face lookups.
+    pub fn face_index(&self, dim: usize) -> FaceIndex {
+        FaceIndex::new(self.sorted_k_faces(dim))
+    }
+
     pub fn euler_characteristic(&self) -> i32{
         if self.dimension() < 0{
             panic!("Empty simplicial complex, Euler characteristic undefined.")
@@ -211,20 +240,26 @@ impl SimplicialComplex {
         gaussian_elimination(self.compute_k_boundary_matrix(dim))
     }
 
-    /// Returns the k-dimensional boundary matrix of the complex. That is, a matrix M with rows indexed by the k faces and columns
-    /// indexed by the (k+1)-faces where $M_{\sigma,\tau} = 1$ iff $\sigma \subset \tau$.
+    /// Returns the k-dimensional boundary matrix of the complex. That is, a matrix M with rows indexed by the (k-1)-faces and columns
+    /// indexed by the k-faces where $M_{\sigma,\tau} = (-1)^i$ iff $\sigma$ is $\tau$ with its ith vertex removed (vertices taken
+    /// in increasing order), and 0 otherwise. Reducing mod 2 recovers the unoriented incidence matrix.
     pub fn compute_k_boundary_matrix(&self, dim: usize) -> DMatrix<i32> {
         //println!("Computing {}-dimensional boundary matrix", dim);
-        let k_minus_one_simplices: Vec<Facet> = self.k_faces(dim-1);
-        let k_simplices: Vec<Facet> = self.k_faces(dim);
+        let k_minus_one_simplices = FaceIndex::new(self.k_faces(dim-1).into_iter().map(|f| f.sort()).collect());
+        let k_simplices: Vec<Facet> = self.k_faces(dim).into_iter().map(|f| f.sort()).collect();
+        Self::boundary_matrix_from_faces(&k_minus_one_simplices, &k_simplices)
+    }
+
+    /// Builds the oriented boundary matrix with rows indexed by `k_minus_one_simplices` and columns by `k_simplices`, in
+    /// the order given. The columns are expected to have their vertices sorted.
+    pub(crate) fn boundary_matrix_from_faces(k_minus_one_simplices: &FaceIndex, k_simplices: &[Facet]) -> DMatrix<i32> {
         let mut bdy_matrix = DMatrix::from_element(k_minus_one_simplices.len(), k_simplices.len(), 0);
         // Populate the matrix based on whether k-simplices are in the boundary of a k+1 simplex
         for (i, facet) in k_simplices.iter().enumerate() {
             let bdy = facet.boundary();
-            for simplex in bdy {
-                let index = k_minus_one_simplices.iter().position(|x| simplex == x);
-                if let Some(j) = index {
-                    bdy_matrix[(j, i)] = 1
+            for (sign_exp, simplex) in bdy.into_iter().enumerate() {
+                if let Some(j) = k_minus_one_simplices.index_of(&simplex) {
+                    bdy_matrix[(j, i)] = (-1i32).pow(sign_exp as u32)
                 }
             }
         }
@@ -232,6 +267,24 @@ impl SimplicialComplex {
         bdy_matrix
     }
 
+    /// Returns the k-dimensional boundary matrix of the complex over Z/2 in sparse column form, see `SparseBinaryMatrix`.
+    pub fn compute_sparse_k_boundary_matrix(&self, dim: usize) -> SparseBinaryMatrix {
+        let k_minus_one_simplices = FaceIndex::new(self.k_faces(dim-1).into_iter().map(|f| f.sort()).collect());
+        let k_simplices: Vec<Facet> = self.k_faces(dim).into_iter().map(|f| f.sort()).collect();
+        Self::sparse_boundary_matrix_from_faces(&k_minus_one_simplices, &k_simplices)
+    }
+
+    /// Builds the sparse Z/2 boundary matrix with rows indexed by `k_minus_one_simplices` and columns by `k_simplices`,
+    /// in the order given. The columns are expected to have their vertices sorted.
+    pub(crate) fn sparse_boundary_matrix_from_faces(k_minus_one_simplices: &FaceIndex, k_simplices: &[Facet]) -> SparseBinaryMatrix {
+        let columns: Vec<Vec<usize>> = k_simplices.iter().map(|facet| {
+            let mut column: Vec<usize> = facet.boundary().iter().filter_map(|simplex| k_minus_one_simplices.index_of(simplex)).collect();
+            column.sort();
+            column
+        }).collect();
+        SparseBinaryMatrix::new(k_minus_one_simplices.len(), columns)
+    }
+
     /// Given a simplex \sigma inside of a complex sc, sc.star(simplex) is the subcomplex consisting of all simplices
     /// that contain \sigma as a face.
     pub fn star(self, simplex: &Facet) -> Self{
@@ -242,7 +295,7 @@ impl SimplicialComplex {
     }
 
     pub fn link(self, simplex: &Facet) -> Self{
-        Self { facets: self.star(&simplex).facets.into_iter().map(|f| f.link(&simplex)).collect()}
+        Self { facets: self.star(simplex).facets.into_iter().map(|f| f.link(simplex)).collect()}
     }
 
     pub fn contains(&self, sc: &SimplicialComplex) -> bool{
@@ -302,11 +355,49 @@ impl SimplicialComplex {
     }
     
 
+    /// Computes the kth betti number of the complex over Z/2 from the sparse k and (k+1) dimensional boundary matrices
+    /// B_k and B_{k+1}, whose ranks are found by sparse column reduction.
+    /// b_k = f_k - rank(B_k) - rank(B_{k+1})
+    pub fn kth_betti_number(&self, dim: usize) -> i32 {
+        let rank_in = if dim == 0 { 0 } else { sparse_rank_mod_2(self.compute_sparse_k_boundary_matrix(dim)) };
+        self.k_faces(dim).len() as i32 - rank_in - sparse_rank_mod_2(self.compute_sparse_k_boundary_matrix(dim+1))
+    }
+
+    /// Computes the betti numbers of the complex over Z/2 using sparse boundary matrices and sparse column reduction.
+    pub fn betti_numbers(&self) -> Vec<i32>{
+        self.betti_numbers_with(BettiAlgorithm::default())
+    }
+
+    /// Computes the betti numbers of the complex over Z/2 with the given algorithm. Reducing to the Morse complex
+    /// first pays off for large complexes, such as random complexes, where most faces are matched and the Morse
+    /// boundary matrices are far smaller than the simplicial ones.
+    pub fn betti_numbers_with(&self, algorithm: BettiAlgorithm) -> Vec<i32>{
+        match algorithm {
+            BettiAlgorithm::Sparse => self.betti_numbers_sparse(),
+            BettiAlgorithm::Dense => self.betti_numbers_dense(),
+            BettiAlgorithm::DiscreteMorse => self.gradient_vector_field().betti_numbers()
+        }
+    }
+
+    /// The faces of each dimension are only enumerated once and shared between neighbouring boundary matrices.
+    fn betti_numbers_sparse(&self) -> Vec<i32>{
+        if self.dimension() < 0{
+            return vec![]
+        }
+        let dim = self.dimension() as usize;
+        let faces: Vec<FaceIndex> = (0..(dim+1)).into_par_iter().map(|k| FaceIndex::new(self.k_faces(k).into_iter().map(|f| f.sort()).collect())).collect();
+        // ranks[k] is the rank of B_k, with B_0 = B_{dim+1} = 0
+        let mut ranks: Vec<i32> = vec![0];
+        ranks.extend((1..(dim+1)).into_par_iter().map(|k| sparse_rank_mod_2(Self::sparse_boundary_matrix_from_faces(&faces[k-1], faces[k].faces()))).collect::<Vec<i32>>());
+        ranks.push(0);
+        (0..(dim+1)).map(|k| faces[k].len() as i32 - ranks[k] - ranks[k+1]).collect()
+    }
+
     /// Computes the kth betti number of the complex by computing the k and (k+1) dimensional
     /// boundary matrices B_k and B_{k+1}. Then reduces these matrices by Gaussian elimination to the
     /// Smith normal form, SB_k, SB_{k+1}.
     /// b_k = row_null(SB_{k+1}) - rank(SB_k)
-    pub fn kth_betti_number(&self, dim: usize) -> i32 {
+    pub fn kth_betti_number_dense(&self, dim: usize) -> i32 {
         if dim == 0 {
             return row_nullity_smith_normal_matrix(&self.compute_reduced_k_boundary_matrix(1))
         }
@@ -314,7 +405,9 @@ impl SimplicialComplex {
         let m2 = &self.compute_reduced_k_boundary_matrix(dim+1);
         row_nullity_smith_normal_matrix(m2) - rank_smith_normal_matrix(m1)
     }
-    pub fn betti_numbers(&self) -> Vec<i32>{
+    /// Computes the betti numbers of the complex over Z/2 by Gaussian elimination on dense boundary matrices, the top
+    /// betti number is recovered from the Euler characteristic.
+    pub fn betti_numbers_dense(&self) -> Vec<i32>{
         if self.dimension() < 0{
             return vec![]
         }
@@ -328,4 +421,81 @@ impl SimplicialComplex {
         betti_numbers
 
     }
-}
\ No newline at end of file
+
+    /// Computes the kth betti number of the complex over the coefficient field F, e.g. `sc.kth_betti_number_over::<Fp<3>>(1)`.
+    /// b_k = f_k - rank(B_k) - rank(B_{k+1}), where the ranks of the oriented boundary matrices are taken over F.
+    pub fn kth_betti_number_over<F: Coefficient>(&self, dim: usize) -> i32 {
+        let num_faces = self.k_faces(dim).len() as i32;
+        let rank_in = if dim == 0 { 0 } else { rank_over::<F>(&self.compute_k_boundary_matrix(dim)) };
+        num_faces - rank_in - rank_over::<F>(&self.compute_k_boundary_matrix(dim+1))
+    }
+
+    /// Computes the betti numbers of the complex over the coefficient field F. Over Fp<2> these agree with
+    /// `betti_numbers`, and by the universal coefficient theorem they differ from the betti numbers over Rational exactly
+    /// when the integral homology has p-torsion.
+    pub fn betti_numbers_over<F: Coefficient>(&self) -> Vec<i32>{
+        if self.dimension() < 0{
+            return vec![]
+        }
+        let dim = self.dimension() as usize;
+        // ranks[k] is the rank of B_k, with B_0 = 0
+        let mut ranks: Vec<i32> = vec![0];
+        ranks.extend((1..(dim+2)).into_par_iter().map(|x| rank_over::<F>(&self.compute_k_boundary_matrix(x))).collect::<Vec<i32>>());
+        (0..(dim+1)).map(|k| self.k_faces(k).len() as i32 - ranks[k] - ranks[k+1]).collect()
+    }
+
+    /// Returns a basis of H_k(X; F) as explicit cycle representatives, each given as the faces in its support together with
+    /// their coefficients. The number of cycles returned is the kth betti number over F.
+    ///
+    /// A basis of the k-cycles is read off from the reduced row echelon form of B_k. These are appended as columns after
+    /// those of B_{k+1} and the matrix is reduced again, the pivot columns coming from cycles are independent modulo the
+    /// boundaries and so represent a basis of homology.
+    pub fn homology_basis_over<F: Coefficient>(&self, dim: usize) -> Vec<Vec<(Facet, F)>>{
+        if self.dimension() < dim as isize {
+            return vec![]
+        }
+        let k_simplices = self.face_index(dim);
+        let cycles: Vec<Vec<F>> = match dim {
+            0 => (0..k_simplices.len()).map(|i| (0..k_simplices.len()).map(|j| if i == j { F::one() } else { F::zero() }).collect()).collect(),
+            _ => kernel_basis_over::<F>(&Self::boundary_matrix_from_faces(&self.face_index(dim-1), k_simplices.faces()))
+        };
+        let boundaries: DMatrix<F> = Self::boundary_matrix_from_faces(&k_simplices, &self.sorted_k_faces(dim+1)).map(F::from_i32);
+        independent_modulo(&boundaries, &cycles).into_iter()
+            .map(|j| {
+                cycles[j].iter().enumerate()
+                    .filter(|(_, c)| !c.is_zero())
+                    .map(|(i, &c)| (k_simplices.faces()[i].clone(), c))
+                    .collect()
+            }).collect()
+    }
+
+    /// Returns a basis of H_k(X; Z/2) as explicit cycle representatives, see `homology_basis_over`. Coefficients are
+    /// all 1, as is the only non-zero option mod 2.
+    pub fn homology_basis(&self, dim: usize) -> Vec<Vec<(Facet, i32)>>{
+        self.homology_basis_over::<Fp<2>>(dim).into_iter()
+            .map(|cycle| cycle.into_iter().map(|(face, c)| (face, c.value() as i32)).collect())
+            .collect()
+    }
+
+    /// Computes the integral homology groups H_0(X; Z), ..., H_d(X; Z) where d is the dimension of the complex.
+    ///
+    /// For each k the oriented boundary matrices B_k and B_{k+1} are reduced to their Smith normal form over Z. Then
+    /// rank(H_k) = f_k - rank(B_k) - rank(B_{k+1}) and the torsion coefficients of H_k are the invariant factors of
+    /// B_{k+1} that are greater than 1. Unlike `betti_numbers`, which works over Z/2, this sees torsion, e.g. the
+    /// real projective plane has H_1 = Z/2.
+    pub fn homology_groups(&self) -> Vec<HomologyGroup>{
+        if self.dimension() < 0{
+            return vec![]
+        }
+        let dim = self.dimension() as usize;
+        // invariant_factors[k] holds the invariant factors of B_{k+1}, i.e. the boundary map from (k+1)-chains to k-chains
+        let invariant_factors: Vec<Vec<i64>> = (1..(dim+2)).into_par_iter().map(|x| integer_smith_normal_form(&self.compute_k_boundary_matrix(x))).collect();
+        (0..(dim+1)).map(|k| {
+            let num_faces = self.k_faces(k).len() as i32;
+            let rank_in = if k == 0 { 0 } else { invariant_factors[k-1].len() as i32 };
+            let rank_out = invariant_factors[k].len() as i32;
+            let torsion: Vec<i64> = invariant_factors[k].iter().filter(|&&d| d > 1).copied().collect();
+            HomologyGroup::new(num_faces - rank_in - rank_out, torsion)
+        }).collect()
+    }
+}
@@ -0,0 +1,53 @@
+use nalgebra::DMatrix;
+
+use super::simplex::Facet;
+use super::simplicial_complex::SimplicialComplex;
+use super::filtration::Filtration;
+
+/// The matrix of pairwise Euclidean distances between points, all of which must have the same dimension.
+pub fn euclidean_distance_matrix(points: &[Vec<f64>]) -> DMatrix<f64> {
+    let n = points.len();
+    assert!(points.iter().all(|p| p.len() == points[0].len()), "All points must have the same dimension.");
+    DMatrix::from_fn(n, n, |i, j| {
+        points[i].iter().zip(points[j].iter()).map(|(x, y)| (x - y).powi(2)).sum::<f64>().sqrt()
+    })
+}
+
+/// The edges i < j of the graph on 0..n with an edge iff distances[(i, j)] <= epsilon.
+fn rips_edges(distances: &DMatrix<f64>, epsilon: f64) -> Vec<(usize, usize)> {
+    assert!(distances.is_square(), "Distance matrix must be square.");
+    let n = distances.nrows();
+    (0..n).flat_map(|i| (i+1..n).map(move |j| (i, j))).filter(|&(i, j)| distances[(i, j)] <= epsilon).collect()
+}
+
+/// The largest distance between two vertices of a simplex, zero for a vertex.
+fn diameter(simplex: &Facet, distances: &DMatrix<f64>) -> f64 {
+    let v = &simplex.vertices;
+    (0..v.len()).flat_map(|i| (i+1..v.len()).map(move |j| (i, j))).map(|(i, j)| distances[(v[i], v[j])]).fold(0.0, f64::max)
+}
+
+/// Vietoris–Rips complexes, the simplices are the sets of at most max_dim+1 points of pairwise distance at most epsilon.
+/// Points are labelled by their index. This is the flag complex of the graph joining points at distance at most epsilon.
+impl SimplicialComplex {
+    pub fn vietoris_rips(points: &[Vec<f64>], epsilon: f64, max_dim: usize) -> Self {
+        Self::vietoris_rips_from_distances(&euclidean_distance_matrix(points), epsilon, max_dim)
+    }
+
+    /// The Vietoris–Rips complex of a finite metric space given by its (symmetric) distance matrix.
+    pub fn vietoris_rips_from_distances(distances: &DMatrix<f64>, epsilon: f64, max_dim: usize) -> Self {
+        let vertices: Vec<usize> = (0..distances.nrows()).collect();
+        Self::flag_complex_with_vertices(&vertices, &rips_edges(distances, epsilon), max_dim)
+    }
+}
+
+/// The Vietoris–Rips filtration up to scale epsilon, each simplex is born at its diameter.
+impl Filtration {
+    pub fn vietoris_rips(points: &[Vec<f64>], epsilon: f64, max_dim: usize) -> Self {
+        Self::vietoris_rips_from_distances(&euclidean_distance_matrix(points), epsilon, max_dim)
+    }
+
+    pub fn vietoris_rips_from_distances(distances: &DMatrix<f64>, epsilon: f64, max_dim: usize) -> Self {
+        let complex = SimplicialComplex::vietoris_rips_from_distances(distances, epsilon, max_dim);
+        Self::from_fn(complex, |sigma| diameter(sigma, distances))
+    }
+}
@@ -1,6 +1,11 @@
 #[cfg(test)]
 
-use crate::simplicial_complex::{simplex::{Facet, Simplex}, simplicial_complex::SimplicialComplex, hypergraph::Hypergraph};
+use nalgebra::DMatrix;
+use crate::simplicial_complex::{simplex::{Facet, Simplex}, simplicial_complex::{SimplicialComplex, BettiAlgorithm}, hypergraph::Hypergraph};
+use crate::simplicial_complex::homology::HomologyGroup;
+use crate::utils::coefficients::{Coefficient, Fp, Rational, is_prime};
+use crate::simplicial_complex::random_simplicial_complex::{generate_random_simplicial_complex, Model};
+use crate::utils::linear_algebra::{integer_smith_normal_form, rank_over, add_columns_mod_2, sparse_rank_mod_2, SparseBinaryMatrix};
 use crate::{sc, simplex};
 
 #[test]
@@ -87,4 +92,164 @@ fn test_alexander_duality(){
     //sc.betti_numbers() = (2,1,0) reduced this becomes (1,1,0)
     //dual map b_i(X)->b_{3-i}(X*) gives (0,0,1,1) which unreduced is (1,0,1,1)
     assert_eq!(sc.alexander_dual().betti_numbers(), vec![1,0,1,1])
-}
\ No newline at end of file
+}
+
+fn real_projective_plane() -> SimplicialComplex {
+    sc![vec![1,2,3], vec![1,3,4], vec![1,4,5], vec![1,5,6], vec![1,2,6],
+        vec![2,3,5], vec![2,4,5], vec![2,4,6], vec![3,4,6], vec![3,5,6]]
+}
+
+fn torus() -> SimplicialComplex {
+    let mut facets: Vec<Vec<usize>> = Vec::new();
+    for i in 0..7{
+        facets.push(vec![i, (i+1) % 7, (i+3) % 7]);
+        facets.push(vec![i, (i+2) % 7, (i+3) % 7]);
+    }
+    SimplicialComplex::new_from_vec(facets)
+}
+
+#[test]
+fn test_integer_smith_normal_form(){
+    let m = DMatrix::from_row_slice(3, 3, &[2, 4, 4, -6, 6, 12, 10, -4, -16]);
+    assert_eq!(integer_smith_normal_form(&m), vec![2, 6, 12]);
+}
+
+#[test]
+fn test_homology_groups_torsion(){
+    let rp2 = real_projective_plane();
+    assert_eq!(rp2.betti_numbers(), vec![1,1,1]);
+    assert_eq!(rp2.homology_groups(), vec![HomologyGroup::new(1, vec![]), HomologyGroup::new(0, vec![2]), HomologyGroup::new(0, vec![])]);
+    assert_eq!(rp2.homology_groups()[1].to_string(), "Z/2");
+}
+
+#[test]
+fn test_homology_groups_torsion_free(){
+    assert_eq!(torus().homology_groups(), vec![HomologyGroup::new(1, vec![]), HomologyGroup::new(2, vec![]), HomologyGroup::new(1, vec![])]);
+    let sigma: Facet = simplex![0,1,2,3,4];
+    let sphere = sigma.boundary_as_complex();
+    let ranks: Vec<i32> = sphere.homology_groups().iter().map(|h| h.rank).collect();
+    assert_eq!(ranks, sphere.betti_numbers());
+}
+
+#[test]
+fn test_betti_numbers_over_fields(){
+    let rp2 = real_projective_plane();
+    assert_eq!(rp2.betti_numbers_over::<Fp<2>>(), vec![1,1,1]);
+    assert_eq!(rp2.betti_numbers_over::<Fp<3>>(), vec![1,0,0]);
+    assert_eq!(rp2.betti_numbers_over::<Rational>(), vec![1,0,0]);
+    assert_eq!(rp2.kth_betti_number_over::<Fp<2>>(2), 1);
+    let t = torus();
+    assert_eq!(t.betti_numbers_over::<Fp<5>>(), t.betti_numbers());
+    assert_eq!(t.betti_numbers_over::<Rational>(), vec![1,2,1]);
+}
+
+#[test]
+fn test_is_prime(){
+    let primes: Vec<u32> = (0..30).filter(|&n| is_prime(n)).collect();
+    assert_eq!(primes, vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+    assert!(is_prime(4294967291));
+}
+
+#[test]
+fn test_homology_basis(){
+    let sigma: Facet = simplex![1,2,3];
+    let circle = sigma.boundary_as_complex();
+    let basis = circle.homology_basis(1);
+    assert_eq!(basis.len(), 1);
+    let mut cycle: Vec<Vec<usize>> = basis[0].iter().map(|(face, _)| face.vertices.clone()).collect();
+    cycle.sort();
+    assert_eq!(cycle, vec![vec![1,2], vec![1,3], vec![2,3]]);
+    assert!(basis[0].iter().all(|(_, c)| *c == 1));
+
+    let t = torus();
+    assert_eq!(t.homology_basis(0).len(), 1);
+    assert_eq!(t.homology_basis(1).len(), 2);
+    assert_eq!(t.homology_basis(2).len(), 1);
+    assert_eq!(t.homology_basis(2)[0].len(), 14);
+    assert_eq!(real_projective_plane().homology_basis_over::<Rational>(1).len(), 0);
+}
+
+#[test]
+fn test_cup_product_distinguishes_torus_from_wedge(){
+    let wedge = sc![vec![1,2], vec![2,3], vec![1,3], vec![1,4], vec![4,5], vec![1,5],
+        vec![1,6,7], vec![1,6,8], vec![1,7,8], vec![6,7,8]];
+    let t = torus();
+    assert_eq!(wedge.betti_numbers(), t.betti_numbers());
+    assert_eq!(t.cohomology_basis_over::<Rational>(1).len(), 2);
+
+    let is_zero = |table: Vec<Vec<Vec<Rational>>>| table.iter().flatten().flatten().all(|c| c.is_zero());
+    assert!(is_zero(wedge.cup_product_table_over::<Rational>(1, 1)));
+    let torus_table = t.cup_product_table_over::<Rational>(1, 1);
+    assert!(!is_zero(torus_table.clone()));
+    assert!(torus_table[0][0][0].is_zero());
+    assert_eq!(torus_table[0][1][0], -torus_table[1][0][0]);
+}
+
+#[test]
+fn test_relative_betti_numbers(){
+    let sigma: Facet = simplex![1,2,3];
+    let disk = sc![vec![1,2,3]];
+    let circle = sigma.boundary_as_complex();
+    assert_eq!(disk.relative_betti_numbers(&circle), vec![0,0,1]);
+    assert_eq!(circle.relative_betti_numbers(&sc![vec![1], vec![2]]), vec![0,2]);
+    assert_eq!(torus().relative_betti_numbers(&SimplicialComplex::new_from_vec(vec![])), torus().betti_numbers());
+
+    let connecting_maps = disk.connecting_maps_over::<Rational>(&circle);
+    assert_eq!(connecting_maps.iter().map(|m| m.rank()).collect::<Vec<i32>>(), vec![0, 1]);
+    let connecting_map = circle.connecting_map_over::<Fp<2>>(&sc![vec![1], vec![2]], 1);
+    assert_eq!((connecting_map.matrix.nrows(), connecting_map.matrix.ncols()), (2, 2));
+    assert_eq!(connecting_map.rank(), 1);
+}
+
+#[test]
+#[should_panic]
+fn test_relative_betti_numbers_not_subcomplex(){
+    sc![vec![1,2,3]].relative_betti_numbers(&sc![vec![3,4]]);
+}
+
+#[test]
+#[should_panic(expected = "not contained")]
+fn test_relative_betti_numbers_over_not_subcomplex(){
+    sc![vec![1,2,3]].relative_betti_numbers_over::<Rational>(&sc![vec![3,4]]);
+}
+
+#[test]
+#[should_panic(expected = "not contained")]
+fn test_relative_homology_basis_not_subcomplex(){
+    sc![vec![1,2,3]].relative_homology_basis_over::<Fp<2>>(&sc![vec![3,4]], 1);
+}
+
+#[test]
+#[should_panic(expected = "not contained")]
+fn test_connecting_map_not_subcomplex(){
+    sc![vec![1,2,3]].connecting_map_over::<Fp<2>>(&sc![vec![3,4]], 1);
+}
+
+#[test]
+#[should_panic(expected = "not contained")]
+fn test_connecting_maps_not_subcomplex(){
+    // A vertex has no connecting maps, but the pair is still rejected
+    sc![vec![1]].connecting_maps_over::<Fp<2>>(&sc![vec![2]]);
+}
+
+#[test]
+fn test_sparse_column_reduction(){
+    assert_eq!(add_columns_mod_2(&[0, 2, 5], &[1, 2, 6]), vec![0, 1, 5, 6]);
+    let m = SparseBinaryMatrix::new(3, vec![vec![0, 1], vec![1, 2], vec![0, 2]]);
+    assert_eq!(sparse_rank_mod_2(m.clone()), 2);
+    assert_eq!(rank_over::<Fp<2>>(&m.to_dense()), 2);
+}
+
+#[test]
+fn test_sparse_betti_numbers_agree_with_dense(){
+    for sc in [real_projective_plane(), torus(), sc![vec![0,1,2,3], vec![3,4], vec![5]]]{
+        assert_eq!(sc.betti_numbers(), sc.betti_numbers_dense());
+        assert_eq!(sc.betti_numbers(), sc.betti_numbers_with(BettiAlgorithm::Dense));
+        assert_eq!(sc.kth_betti_number(1), sc.kth_betti_number_dense(1));
+    }
+    let model = Model::Lower { num_vertices: 12, prob_vec: vec![1.0, 0.6, 0.8, 0.9] };
+    for _ in 0..5{
+        let sc = generate_random_simplicial_complex(&model);
+        assert_eq!(sc.betti_numbers(), sc.betti_numbers_over::<Fp<2>>());
+    }
+}
@@ -0,0 +1,70 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use crate::sc;
+use crate::simplicial_complex::configuration_model::{ConfigurationModelChain, sample_configuration_model_with_rng};
+use crate::simplicial_complex::random_simplicial_complex::{generate_random_simplicial_complex_with_rng, Model};
+use crate::simplicial_complex::simplicial_complex::SimplicialComplex;
+use crate::simplicial_complex::simplex::Simplex;
+use crate::utils::utils::is_sorted_subset;
+
+fn observed() -> SimplicialComplex {
+    sc![vec![0,1,2], vec![1,2,3], vec![3,4], vec![4,5], vec![5,6,7], vec![6,8], vec![8,9], vec![2,9], vec![0,7], vec![1,5,9]]
+}
+
+fn assert_valid_with_same_sequences(sc: &SimplicialComplex, observed: &SimplicialComplex){
+    assert_eq!(sc.facet_size_sequence(), observed.facet_size_sequence());
+    assert_eq!(sc.vertex_facet_degrees(), observed.vertex_facet_degrees());
+    for (i, f) in sc.facets.iter().enumerate(){
+        for (j, g) in sc.facets.iter().enumerate(){
+            assert!(i == j || !is_sorted_subset(&f.clone().sort().vertices, &g.clone().sort().vertices));
+        }
+    }
+}
+
+#[test]
+fn test_degree_sequences(){
+    let sc = sc![vec![0,1,2], vec![2,3], vec![3]];
+    assert_eq!(sc.facet_size_sequence(), vec![3, 2]);
+    assert_eq!(sc.vertex_facet_degrees().get(&2), Some(&2));
+    assert!(is_sorted_subset(&[1, 3], &[0, 1, 2, 3]));
+    assert!(!is_sorted_subset(&[1, 4], &[0, 1, 2, 3]));
+}
+
+#[test]
+fn test_configuration_model_chain_preserves_sequences(){
+    let observed = observed();
+    let mut rng = StdRng::seed_from_u64(31);
+    let mut chain = ConfigurationModelChain::new(&observed);
+    let accepted = chain.run_with_rng(500, &mut rng);
+    assert!(accepted > 0 && chain.acceptance_rate() > 0.0);
+    let sample = chain.complex();
+    assert_valid_with_same_sequences(&sample, &observed);
+    assert_ne!(sample, observed);
+
+    let sample = generate_random_simplicial_complex_with_rng(&Model::Configuration { observed: observed.clone(), num_steps: 200 }, &mut rng);
+    assert_valid_with_same_sequences(&sample, &observed);
+}
+
+#[test]
+fn test_configuration_model_rejection_sampler(){
+    let observed = observed();
+    let mut rng = StdRng::seed_from_u64(37);
+    let sample = sample_configuration_model_with_rng(&observed, 10000, &mut rng).unwrap();
+    assert_valid_with_same_sequences(&sample, &observed);
+    // The full simplex on 3 vertices cannot be rewired into anything else
+    assert_eq!(sample_configuration_model_with_rng(&sc![vec![0,1,2]], 10, &mut rng), Some(sc![vec![0,1,2]]));
+    // Every edge of a star must contain its centre, so the only rewiring swaps two leaves and gives the star back
+    let star = sc![vec![0,1], vec![0,2], vec![0,3]];
+    assert_eq!(sample_configuration_model_with_rng(&star, 1000, &mut rng), Some(star.clone()));
+    let mut chain = ConfigurationModelChain::new(&star);
+    assert_eq!(chain.run_with_rng(50, &mut rng), 50);
+    assert_eq!(chain.complex(), star);
+}
+
+#[test]
+#[should_panic(expected = "distinct and maximal")]
+fn test_configuration_model_chain_rejects_non_maximal_facets(){
+    let sc = SimplicialComplex { facets: vec![Simplex::new(vec![0,1,2]), Simplex::new(vec![0,1]), Simplex::new(vec![2,3])] };
+    ConfigurationModelChain::new(&sc);
+}
@@ -0,0 +1,108 @@
+use std::collections::{HashMap, HashSet};
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use crate::sc;
+use crate::simplicial_complex::random_simplicial_complex::{generate_random_simplicial_complex_with_rng, Model};
+use crate::simplicial_complex::simplex::{Facet, Simplex};
+use crate::simplicial_complex::simplicial_complex::{SimplicialComplex, BettiAlgorithm};
+
+fn torus() -> SimplicialComplex {
+    let mut facets: Vec<Vec<usize>> = Vec::new();
+    for i in 0..7{
+        facets.push(vec![i, (i+1) % 7, (i+3) % 7]);
+        facets.push(vec![i, (i+2) % 7, (i+3) % 7]);
+    }
+    SimplicialComplex::new_from_vec(facets)
+}
+
+fn real_projective_plane() -> SimplicialComplex {
+    sc![vec![1,2,3], vec![1,3,4], vec![1,4,5], vec![1,5,6], vec![1,2,6],
+        vec![2,3,5], vec![2,4,5], vec![2,4,6], vec![3,4,6], vec![3,5,6]]
+}
+
+/// Checks the pairs and critical simplices partition the faces, pairs are face and coface, and there are no closed
+/// gradient paths.
+fn assert_gradient_vector_field(sc: &SimplicialComplex){
+    let field = sc.gradient_vector_field();
+    let pairs = field.pairs();
+    let mut seen: HashSet<Facet> = field.critical_simplices().into_iter().flatten().collect();
+    for (sigma, tau) in pairs.iter(){
+        assert!(tau.boundary().contains(sigma));
+        assert!(seen.insert(sigma.clone()));
+        assert!(seen.insert(tau.clone()));
+    }
+    let num_faces: usize = (0..=sc.dimension()).map(|k| sc.face_index(k as usize).len()).sum();
+    assert_eq!(seen.len(), num_faces);
+
+    // Kahn's algorithm on the gradient paths sigma -> tau -> rho for rho another face of tau
+    let mut successors: HashMap<Facet, Vec<Facet>> = HashMap::new();
+    let mut in_degree: HashMap<Facet, usize> = HashMap::new();
+    for (sigma, tau) in pairs.iter(){
+        for rho in tau.boundary().into_iter().filter(|rho| rho != sigma){
+            *in_degree.entry(rho.clone()).or_insert(0) += 1;
+            successors.entry(sigma.clone()).or_default().push(rho);
+        }
+    }
+    let mut stack: Vec<Facet> = seen.iter().filter(|f| !in_degree.contains_key(*f)).cloned().collect();
+    let mut visited = 0;
+    while let Some(sigma) = stack.pop(){
+        visited += 1;
+        for rho in successors.get(&sigma).into_iter().flatten(){
+            let d = in_degree.get_mut(rho).unwrap();
+            *d -= 1;
+            if *d == 0 {
+                stack.push(rho.clone());
+            }
+        }
+    }
+    assert_eq!(visited, seen.len());
+}
+
+#[test]
+fn test_collapsible_complexes_have_one_critical_vertex(){
+    let simplex = sc![vec![0,1,2,3,4]];
+    assert_eq!(simplex.gradient_vector_field().morse_numbers(), vec![1, 0, 0, 0, 0]);
+    let tree = sc![vec![0,1], vec![1,2], vec![1,3], vec![3,4]];
+    assert_eq!(tree.gradient_vector_field().morse_numbers(), vec![1, 0]);
+    assert_gradient_vector_field(&simplex);
+    assert_gradient_vector_field(&tree);
+}
+
+#[test]
+fn test_morse_betti_numbers(){
+    let simplex: Facet = Simplex::new((0..5).collect());
+    let sphere = SimplicialComplex::new(simplex.boundary());
+    for sc in [torus(), real_projective_plane(), sphere, sc![vec![0,1,2,3], vec![3,4], vec![5]]]{
+        assert_gradient_vector_field(&sc);
+        assert_eq!(sc.betti_numbers_with(BettiAlgorithm::DiscreteMorse), sc.betti_numbers());
+        let morse_numbers = sc.gradient_vector_field().morse_numbers();
+        let euler: i64 = morse_numbers.iter().enumerate().map(|(k, &c)| if k % 2 == 0 { c as i64 } else { -(c as i64) }).sum();
+        assert_eq!(euler, sc.betti_numbers().iter().enumerate().map(|(k, &b)| if k % 2 == 0 { b as i64 } else { -(b as i64) }).sum::<i64>());
+    }
+}
+
+#[test]
+fn test_morse_betti_numbers_of_random_complexes(){
+    let mut rng = StdRng::seed_from_u64(25);
+    let model = Model::Lower { num_vertices: 14, prob_vec: vec![1.0, 0.5, 0.7, 0.8] };
+    for _ in 0..10{
+        let sc = generate_random_simplicial_complex_with_rng(&model, &mut rng);
+        assert_gradient_vector_field(&sc);
+        let betti_numbers = sc.betti_numbers();
+        assert_eq!(sc.betti_numbers_with(BettiAlgorithm::DiscreteMorse), betti_numbers);
+        // Weak Morse inequalities
+        for (c, b) in sc.gradient_vector_field().morse_numbers().iter().zip(betti_numbers.iter()){
+            assert!(*c as i32 >= *b);
+        }
+    }
+}
+
+#[test]
+fn test_morse_boundary_matrix_squares_to_zero(){
+    let field = real_projective_plane().gradient_vector_field();
+    let d1 = field.morse_boundary_matrix(1).to_dense();
+    let d2 = field.morse_boundary_matrix(2).to_dense();
+    assert!((d1 * d2).iter().all(|x| x % 2 == 0));
+}
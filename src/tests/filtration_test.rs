@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+
+use crate::simplicial_complex::filtration::{Filtration, PersistenceInterval};
+use crate::simplicial_complex::simplex::{Facet, Simplex};
+use crate::simplicial_complex::simplicial_complex::SimplicialComplex;
+use crate::simplicial_complex::vietoris_rips::euclidean_distance_matrix;
+use crate::{sc, simplex};
+
+fn filled_triangle() -> Filtration {
+    let mut birth_times: HashMap<Facet, f64> = HashMap::new();
+    for v in 0..3{
+        birth_times.insert(simplex![v], 0.0);
+    }
+    birth_times.insert(simplex![0,1], 1.0);
+    birth_times.insert(simplex![1,2], 2.0);
+    birth_times.insert(simplex![0,2], 3.0);
+    birth_times.insert(simplex![0,1,2], 4.0);
+    Filtration::new(sc![vec![0,1,2]], birth_times)
+}
+
+#[test]
+fn test_triangle_barcodes(){
+    let filtration = filled_triangle();
+    let mut h0 = filtration.kth_barcode(0);
+    h0.sort_by(|a, b| a.death.total_cmp(&b.death));
+    assert_eq!(h0, vec![PersistenceInterval::new(0.0, 1.0), PersistenceInterval::new(0.0, 2.0), PersistenceInterval::new(0.0, f64::INFINITY)]);
+    assert_eq!(filtration.kth_barcode(1), vec![PersistenceInterval::new(3.0, 4.0)]);
+    assert!(filtration.kth_barcode(2).is_empty());
+    assert_eq!(filtration.birth_time(&simplex![2,0]), Some(3.0));
+}
+
+#[test]
+fn test_essential_classes_match_betti_numbers(){
+    let sigma: Facet = simplex![0,1,2,3];
+    let sphere = sigma.boundary_as_complex();
+    let filtration = Filtration::from_fn(sphere.clone(), |f| f.dimension() as f64);
+    let essential: Vec<i32> = filtration.barcodes().iter().map(|b| b.iter().filter(|i| i.is_essential()).count() as i32).collect();
+    assert_eq!(essential, sphere.betti_numbers());
+}
+
+#[test]
+#[should_panic]
+fn test_face_born_after_coface(){
+    Filtration::from_fn(sc![vec![0,1]], |f| -(f.dimension() as f64));
+}
+
+fn unit_square() -> Vec<Vec<f64>> {
+    vec![vec![0.0, 0.0], vec![1.0, 0.0], vec![1.0, 1.0], vec![0.0, 1.0]]
+}
+
+#[test]
+fn test_vietoris_rips(){
+    let points = unit_square();
+    assert_eq!(SimplicialComplex::vietoris_rips(&points, 1.0, 3).betti_numbers(), vec![1,1]);
+    assert_eq!(SimplicialComplex::vietoris_rips(&points, 1.5, 3), sc![vec![0,1,2,3]]);
+    assert_eq!(SimplicialComplex::vietoris_rips(&points, 1.5, 1).betti_numbers(), vec![1,3]);
+    assert_eq!(SimplicialComplex::vietoris_rips(&points, 0.5, 2).betti_numbers(), vec![4]);
+    let distances = euclidean_distance_matrix(&points);
+    assert_eq!(SimplicialComplex::vietoris_rips_from_distances(&distances, 1.0, 2), SimplicialComplex::vietoris_rips(&points, 1.0, 2));
+}
+
+#[test]
+fn test_vietoris_rips_filtration(){
+    let filtration = Filtration::vietoris_rips(&unit_square(), 2.0, 2);
+    assert_eq!(filtration.kth_barcode(1), vec![PersistenceInterval::new(1.0, 2f64.sqrt())]);
+    assert_eq!(filtration.kth_barcode(0).len(), 4);
+    assert_eq!(filtration.birth_time(&simplex![0,2]), Some(2f64.sqrt()));
+}
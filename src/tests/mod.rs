@@ -1,4 +1,22 @@
 #[cfg(test)]
+#[allow(clippy::empty_line_after_outer_attr)]
 mod betti_number_test;
 #[cfg(test)]
-mod simplicial_complex_test;
\ No newline at end of file
+#[allow(clippy::bool_assert_comparison)]
+mod simplicial_complex_test;
+#[cfg(test)]
+mod filtration_test;
+#[cfg(test)]
+mod random_complex_test;
+#[cfg(test)]
+mod random_process_test;
+#[cfg(test)]
+mod statistics_test;
+#[cfg(test)]
+mod configuration_model_test;
+#[cfg(test)]
+mod model_fitting_test;
+#[cfg(test)]
+mod persistence_diagram_test;
+#[cfg(test)]
+mod discrete_morse_test;
\ No newline at end of file
@@ -0,0 +1,76 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use crate::sc;
+use crate::simplicial_complex::model_fitting::{fit_lower, fit_upper, fit_linial_meshulam, log_likelihood};
+use crate::simplicial_complex::random_simplicial_complex::{generate_random_simplicial_complex, generate_random_simplicial_complex_with_rng, Model};
+use crate::simplicial_complex::simplicial_complex::SimplicialComplex;
+
+#[test]
+fn test_fit_closed_forms(){
+    // A filled triangle, a hollow triangle on 2, 3, 4 and a pendant edge
+    let sc = sc![vec![0,1,2], vec![2,3], vec![3,4], vec![2,4], vec![4,5]];
+    assert_eq!(sc.f_vector(), vec![6, 7, 1]);
+    // 8 non-edges, and the triangles 0 1 2 and 2 3 4 have full boundary
+    assert_eq!(fit_lower(&sc), Model::Lower { num_vertices: 6, prob_vec: vec![1.0, 7.0 / 15.0, 0.5] });
+    // Facets: 4 edges, 1 triangle. Non-faces: 8 edges, 19 triangles
+    assert_eq!(fit_upper(&sc), Model::Upper { num_vertices: 6, prob_vec: vec![0.0, 4.0 / 12.0, 1.0 / 20.0] });
+
+    let lm = sc![vec![0,1,2], vec![0,3], vec![1,3], vec![2,3]];
+    assert_eq!(fit_linial_meshulam(&lm), Model::LinialMeshulam { num_vertices: 4, dimension: 2, prob: 0.25 });
+}
+
+#[test]
+fn test_fits_on_many_vertices(){
+    // C(500, 10) overflows usize, so the numbers of possible faces are counted in floats
+    let mut facets: Vec<Vec<usize>> = vec![(0..10).collect()];
+    facets.extend((10..500).map(|v| vec![v]));
+    let sc = SimplicialComplex::new_from_vec(facets);
+    let upper = fit_upper(&sc);
+    let Model::Upper { num_vertices: 500, prob_vec } = &upper else { panic!("Expected an Upper model.") };
+    let possible_facets: f64 = (0..10).map(|i| (500 - i) as f64 / (i + 1) as f64).product();
+    assert!((prob_vec[9] * possible_facets - 1.0).abs() < 1e-9);
+    assert!(log_likelihood(&upper, &sc).is_finite());
+    let larger = Model::Upper { num_vertices: 500, prob_vec: vec![0.5; 12] };
+    assert!(log_likelihood(&larger, &sc) < log_likelihood(&upper, &sc));
+}
+
+#[test]
+#[should_panic]
+fn test_fit_linial_meshulam_needs_full_skeleton(){
+    fit_linial_meshulam(&sc![vec![0,1,2], vec![2,3]]);
+}
+
+#[test]
+#[should_panic]
+fn test_linial_meshulam_log_likelihood_needs_positive_dimension(){
+    log_likelihood(&Model::LinialMeshulam { num_vertices: 3, dimension: 0, prob: 0.5 }, &sc![vec![0], vec![1]]);
+}
+
+#[test]
+fn test_fits_recover_parameters_and_maximise_likelihood(){
+    let mut rng = StdRng::seed_from_u64(41);
+    let truth = Model::LinialMeshulam { num_vertices: 20, dimension: 2, prob: 0.2 };
+    let sc = generate_random_simplicial_complex_with_rng(&truth, &mut rng);
+    let fitted = fit_linial_meshulam(&sc);
+    let Model::LinialMeshulam { prob, .. } = fitted else { panic!() };
+    assert!((prob - 0.2).abs() < 0.03);
+    assert!(log_likelihood(&fitted, &sc) >= log_likelihood(&truth, &sc));
+    assert_eq!(log_likelihood(&truth, &sc![vec![0, 1]]), f64::NEG_INFINITY);
+
+    let truth = Model::Lower { num_vertices: 25, prob_vec: vec![1.0, 0.5, 0.6] };
+    let sc = generate_random_simplicial_complex_with_rng(&truth, &mut rng);
+    let fitted = fit_lower(&sc);
+    let Model::Lower { ref prob_vec, .. } = fitted else { panic!() };
+    assert!((prob_vec[1] - 0.5).abs() < 0.1 && (prob_vec[2] - 0.6).abs() < 0.1);
+    let lower_likelihood = log_likelihood(&fitted, &sc);
+    assert!(lower_likelihood >= log_likelihood(&truth, &sc));
+    assert!(lower_likelihood >= log_likelihood(&Model::Lower { num_vertices: 25, prob_vec: vec![1.0, prob_vec[1] + 0.01, prob_vec[2]] }, &sc));
+
+    let truth = Model::Upper { num_vertices: 15, prob_vec: vec![1.0, 0.1, 0.02] };
+    let sc = generate_random_simplicial_complex_with_rng(&truth, &mut rng);
+    let fitted = fit_upper(&sc);
+    assert!(log_likelihood(&fitted, &sc) >= log_likelihood(&truth, &sc));
+    // The fitted model is an ordinary model
+    let _: SimplicialComplex = generate_random_simplicial_complex(&fitted);
+}
@@ -0,0 +1,137 @@
+use itertools::Itertools;
+use nalgebra::DMatrix;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+
+use crate::simplicial_complex::filtration::{Filtration, PersistenceInterval};
+use crate::simplicial_complex::persistence_diagram::{PersistenceDiagram, pairwise_bottleneck_distances, pairwise_wasserstein_distances};
+use crate::simplicial_complex::persistence_summaries::{PersistenceImage, ImageKernel, Weighting};
+use crate::utils::matching::{hungarian, hopcroft_karp};
+use crate::utils::statistics::erf;
+use crate::utils::utils::linspace;
+
+fn diagram(points: &[(f64, f64)]) -> PersistenceDiagram {
+    PersistenceDiagram::new(points.iter().map(|&(b, d)| PersistenceInterval::new(b, d)).collect())
+}
+
+fn random_diagram(rng: &mut StdRng, size: usize) -> PersistenceDiagram {
+    diagram(&(0..size).map(|_| { let b: f64 = rng.gen(); (b, b + rng.gen::<f64>()) }).collect_vec())
+}
+
+/// Tries every matching, with unmatched points sent to the diagonal
+fn brute_force_distance(a: &PersistenceDiagram, b: &PersistenceDiagram, p: f64) -> f64 {
+    let (a, b) = (a.points(), b.points());
+    let (n, m) = (a.len(), b.len());
+    let cost = |i: usize, j: usize| -> f64 {
+        match (i < n, j < m) {
+            (true, true) => (a[i].birth - b[j].birth).abs().max((a[i].death - b[j].death).abs()),
+            (true, false) => (a[i].death - a[i].birth) / 2.0,
+            (false, true) => (b[j].death - b[j].birth) / 2.0,
+            (false, false) => 0.0
+        }
+    };
+    (0..n+m).permutations(n+m).map(|perm| {
+        // Diagonal copies are interchangeable, so a row of a may take any diagonal column and vice versa
+        let costs = perm.iter().enumerate().map(|(i, &j)| cost(i, j));
+        if p.is_infinite() { costs.fold(0.0, f64::max) } else { costs.map(|c| c.powf(p)).sum::<f64>().powf(1.0 / p) }
+    }).fold(f64::INFINITY, f64::min)
+}
+
+#[test]
+fn test_matching_algorithms(){
+    let cost = DMatrix::from_row_slice(3, 3, &[4.0, 1.0, 3.0, 2.0, 0.0, 5.0, 3.0, 2.0, 2.0]);
+    let assignment = hungarian(&cost);
+    assert_eq!(assignment.iter().enumerate().map(|(i, &j)| cost[(i, j)]).sum::<f64>(), 5.0);
+    assert_eq!(hopcroft_karp(3, &[vec![0, 1], vec![0], vec![0]]), 2);
+    assert_eq!(hopcroft_karp(3, &[vec![1, 2], vec![0], vec![1]]), 3);
+}
+
+#[test]
+fn test_distances_match_brute_force(){
+    let mut rng = StdRng::seed_from_u64(43);
+    for _ in 0..15{
+        let (n, m) = (rng.gen_range(0..4), rng.gen_range(0..4));
+        let (a, b) = (random_diagram(&mut rng, n), random_diagram(&mut rng, m));
+        assert!((a.bottleneck_distance(&b) - brute_force_distance(&a, &b, f64::INFINITY)).abs() < 1e-12);
+        assert!((a.wasserstein_distance(&b, 1.0) - brute_force_distance(&a, &b, 1.0)).abs() < 1e-9);
+        assert!((a.wasserstein_distance(&b, 2.0) - brute_force_distance(&a, &b, 2.0)).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_persistence_diagram_distances(){
+    let a = diagram(&[(0.0, 4.0), (1.0, 2.0), (0.0, f64::INFINITY)]);
+    let b = diagram(&[(0.0, 3.0), (1.0, f64::INFINITY)]);
+    assert_eq!(a.bottleneck_distance(&a), 0.0);
+    // (0, 4) moves to (0, 3), (1, 2) to the diagonal and the essential classes by 1
+    assert_eq!(a.bottleneck_distance(&b), 1.0);
+    assert_eq!(a.wasserstein_distance(&b, 1.0), 2.5);
+    assert_eq!(a.wasserstein_distance(&b, f64::INFINITY), 1.0);
+    assert_eq!(a.bottleneck_distance(&diagram(&[(0.0, 4.0)])), f64::INFINITY);
+
+    let diagrams = vec![a.clone(), b.clone(), diagram(&[(0.5, f64::INFINITY)])];
+    let bottleneck = pairwise_bottleneck_distances(&diagrams);
+    assert_eq!(bottleneck[(0, 1)], 1.0);
+    assert_eq!(bottleneck[(1, 0)], 1.0);
+    assert_eq!(bottleneck[(1, 2)], 1.5);
+    assert_eq!(pairwise_wasserstein_distances(&diagrams, 1.0)[(2, 1)], 2.0);
+}
+
+#[test]
+fn test_filtration_persistence_diagram(){
+    let points = vec![vec![0.0, 0.0], vec![1.0, 0.0], vec![0.0, 1.0], vec![1.0, 1.0]];
+    let filtration = Filtration::vietoris_rips(&points, 2.0, 2);
+    let diagram = filtration.persistence_diagram(1);
+    assert_eq!(diagram.points(), filtration.kth_barcode(1).as_slice());
+    assert_eq!(filtration.persistence_diagram(0).bottleneck_distance(&filtration.persistence_diagram(0)), 0.0);
+}
+
+#[test]
+fn test_betti_curves_and_landscapes(){
+    let d = diagram(&[(0.0, 2.0), (1.0, f64::INFINITY)]);
+    assert_eq!(d.betti_curve(&[0.0, 1.0, 2.0, 3.0]), vec![1.0, 2.0, 1.0, 1.0]);
+    let d = diagram(&[(0.0, 4.0), (1.0, 3.0), (0.0, f64::INFINITY)]);
+    let grid = linspace(0.0, 4.0, 5);
+    assert_eq!(d.landscapes(3, &grid), vec![vec![0.0, 1.0, 2.0, 1.0, 0.0], vec![0.0, 0.0, 1.0, 0.0, 0.0], vec![0.0; 5]]);
+    assert_eq!(d.landscape_vector(2, &grid).len(), 10);
+
+    let points = vec![vec![0.0, 0.0], vec![1.0, 0.0], vec![0.0, 1.0], vec![1.0, 1.0], vec![3.0, 3.0]];
+    let filtration = Filtration::vietoris_rips(&points, 5.0, 2);
+    let curves = filtration.betti_curves(&[0.0, 10.0]);
+    assert_eq!(curves[0], vec![5.0, 1.0]);
+    assert_eq!(filtration.persistence_landscapes(1, 1, &[0.0]), vec![vec![0.0]]);
+}
+
+#[test]
+fn test_persistence_images(){
+    assert!((erf(1.0) - 0.8427007929).abs() < 2e-7);
+    assert!(erf(-1.0) == -erf(1.0));
+    let d = diagram(&[(0.35, 0.85), (0.5, f64::INFINITY)]);
+    // A small uniform kernel puts all of the point's mass in the pixel containing (birth, persistence) = (0.35, 0.5)
+    let image = PersistenceImage::new((0.0, 1.0), (0.0, 1.0), (10, 5))
+        .with_kernel(ImageKernel::Uniform { half_width: 0.01 })
+        .with_weighting(Weighting::Constant);
+    let pixels = image.compute(&d);
+    assert_eq!(pixels.shape(), (5, 10));
+    assert!((pixels[(2, 3)] - 1.0).abs() < 1e-12);
+    assert!((pixels.sum() - 1.0).abs() < 1e-12);
+
+    let gaussian = PersistenceImage::new((-2.0, 3.0), (-2.0, 3.0), (20, 20));
+    let pixels = gaussian.compute(&d);
+    // Almost all of the Gaussian lies in the image, weighted by 0.5 / 3
+    assert!((pixels.sum() - 0.5 / 3.0).abs() < 1e-4);
+    assert_eq!(gaussian.vectorise(&d).as_slice(), pixels.as_slice());
+    assert_eq!(Weighting::Power { exponent: 2.0 }.weight(3.0), 9.0);
+}
+
+#[test]
+#[should_panic]
+fn test_persistence_image_rejects_zero_width_kernel(){
+    PersistenceImage::new((0.0, 1.0), (0.0, 1.0), (4, 4)).with_kernel(ImageKernel::Gaussian { standard_deviation: 0.0 });
+}
+
+#[test]
+#[should_panic]
+fn test_linear_weighting_rejects_zero_max_persistence(){
+    Weighting::Linear { max_persistence: 0.0 }.weight(0.5);
+}
@@ -0,0 +1,190 @@
+use itertools::Itertools;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use num_integer::binomial;
+
+use crate::sc;
+use crate::simplicial_complex::random_simplicial_complex::{generate_random_simplicial_complex, generate_random_simplicial_complex_with_rng,
+    generate_many_random_simplicial_complexes_with_seed, generate_many_random_betti_numbers_with_seed, par_generate_random_hypergraph_with_seed, generate_random_uniform_hypergraph_with_rng,
+    generate_random_inhomogeneous_hypergraph_with_rng, sample_inhomogeneous_subsets_with_rng, Kernel, Model};
+use crate::utils::utils::{derive_seed, unrank_combination, rank_combination, sample_subsets_with_rng, sample_fixed_size_subsets_with_rng, get_subvectors};
+use crate::simplicial_complex::simplicial_complex::SimplicialComplex;
+use crate::simplicial_complex::random_geometric_complex::{Space, GeometricComplex, sample_points, distance_matrix, geometric_complex};
+
+#[test]
+fn test_clique_model(){
+    let full = generate_random_simplicial_complex(&Model::Clique { num_vertices: 6, prob: 1.0 });
+    assert_eq!(full, sc![(0..6).collect_vec()]);
+    let empty = generate_random_simplicial_complex(&Model::Clique { num_vertices: 6, prob: 0.0 });
+    assert_eq!(empty.betti_numbers(), vec![6]);
+    let sc = generate_random_simplicial_complex(&Model::Clique { num_vertices: 30, prob: 0.3 });
+    assert_eq!(sc.face_index(0).len(), 30);
+    assert_eq!(sc, SimplicialComplex::flag_complex_with_vertices(&(0..30).collect_vec(), &sc.k_faces(1).iter().map(|e| (e.vertices[0], e.vertices[1])).collect_vec(), 30));
+}
+
+#[test]
+fn test_geometric_models(){
+    for space in [Space::Cube, Space::Torus, Space::Sphere]{
+        for complex in [GeometricComplex::Cech, GeometricComplex::VietorisRips]{
+            let isolated = generate_random_simplicial_complex(&Model::Geometric { num_vertices: 10, dimension: 2, radius: 0.0, space, complex, max_dim: 2 });
+            assert_eq!(isolated.betti_numbers(), vec![10]);
+        }
+        let full = generate_random_simplicial_complex(&Model::Geometric { num_vertices: 8, dimension: 2, radius: 1.5, space, complex: GeometricComplex::VietorisRips, max_dim: 7 });
+        assert_eq!(full, sc![(0..8).collect_vec()]);
+    }
+    let points = sample_points(20, 3, Space::Sphere);
+    assert!(points.iter().all(|p| p.len() == 4 && (p.iter().map(|x| x * x).sum::<f64>() - 1.0).abs() < 1e-9));
+}
+
+#[test]
+fn test_torus_distance_wraps(){
+    let points = vec![vec![0.05, 0.5], vec![0.95, 0.5]];
+    let distances = distance_matrix(&points, Space::Torus);
+    assert!((distances[(0, 1)] - 0.1).abs() < 1e-9);
+    assert_eq!(geometric_complex(&points, Space::Torus, GeometricComplex::Cech, 0.06, 1), sc![vec![0,1]]);
+    assert_eq!(geometric_complex(&points, Space::Cube, GeometricComplex::Cech, 0.06, 1).betti_numbers(), vec![2]);
+}
+
+#[test]
+fn test_seeded_generation_is_reproducible(){
+    let model = Model::Lower { num_vertices: 10, prob_vec: vec![1.0, 0.5, 0.5] };
+    let first = generate_many_random_simplicial_complexes_with_seed(20, Model::Lower { num_vertices: 10, prob_vec: vec![1.0, 0.5, 0.5] }, 42);
+    let second = generate_many_random_simplicial_complexes_with_seed(20, Model::Lower { num_vertices: 10, prob_vec: vec![1.0, 0.5, 0.5] }, 42);
+    assert_eq!(first, second);
+    // Any single sample can be regenerated on its own
+    let mut rng = StdRng::seed_from_u64(derive_seed(42, 7));
+    assert_eq!(generate_random_simplicial_complex_with_rng(&model, &mut rng), first[7]);
+
+    let hg1 = par_generate_random_hypergraph_with_seed(12, vec![0.9, 0.5, 0.5, 0.5], 3);
+    let hg2 = par_generate_random_hypergraph_with_seed(12, vec![0.9, 0.5, 0.5, 0.5], 3);
+    assert_eq!((hg1.vertices, hg1.hyperedges), (hg2.vertices, hg2.hyperedges));
+
+    let geometric = Model::Geometric { num_vertices: 15, dimension: 2, radius: 0.2, space: Space::Sphere, complex: GeometricComplex::Cech, max_dim: 2 };
+    let mut rng1 = StdRng::seed_from_u64(1);
+    let mut rng2 = StdRng::seed_from_u64(1);
+    assert_eq!(generate_random_simplicial_complex_with_rng(&geometric, &mut rng1), generate_random_simplicial_complex_with_rng(&geometric, &mut rng2));
+    assert_eq!(generate_many_random_betti_numbers_with_seed(10, Model::Clique { num_vertices: 12, prob: 0.4 }, 5),
+        generate_many_random_betti_numbers_with_seed(10, Model::Clique { num_vertices: 12, prob: 0.4 }, 5));
+}
+
+#[test]
+fn test_unrank_combination_matches_lexicographic_order(){
+    let subsets = get_subvectors(&(0..9).collect_vec(), 4);
+    assert_eq!(binomial(9, 4), subsets.len());
+    for (rank, subset) in subsets.iter().enumerate(){
+        assert_eq!(&unrank_combination(rank as u128, 9, 4), subset);
+    }
+    assert_eq!(binomial(200u128, 4), 64684950);
+}
+
+#[test]
+fn test_sparse_subset_sampling(){
+    let mut rng = StdRng::seed_from_u64(11);
+    assert_eq!(sample_subsets_with_rng(8, 3, 1.0, &mut rng).len(), 56);
+    assert!(sample_subsets_with_rng(8, 3, 0.0, &mut rng).is_empty());
+    let sample = sample_subsets_with_rng(40, 3, 0.05, &mut rng);
+    // Sorted, distinct and of roughly the expected size C(40, 3) * 0.05 = 494
+    assert!(sample.windows(2).all(|w| w[0] < w[1]));
+    assert!(sample.len() > 394 && sample.len() < 594);
+
+    let lm = generate_random_simplicial_complex_with_rng(&Model::LinialMeshulam { num_vertices: 60, dimension: 3, prob: 1.0 / 60.0 }, &mut rng);
+    assert!(lm.contains_full_k_skeleton(2));
+    assert!(lm.dimension() <= 3);
+    let pure = generate_random_simplicial_complex_with_rng(&Model::Pure { num_vertices: 10, dimension: 2, prob: 1.0, include_all_vertices: true }, &mut rng);
+    assert_eq!(pure, SimplicialComplex::new_from_vec(get_subvectors(&(0..10).collect_vec(), 3)));
+}
+
+#[test]
+fn test_fixed_size_models(){
+    let mut rng = StdRng::seed_from_u64(17);
+    let sample = sample_fixed_size_subsets_with_rng(30, 3, 100, &mut rng);
+    assert_eq!(sample.len(), 100);
+    assert!(sample.windows(2).all(|w| w[0] < w[1]));
+    assert_eq!(sample_fixed_size_subsets_with_rng(6, 2, 15, &mut rng), get_subvectors(&(0..6).collect_vec(), 2));
+    // Each single 2-subset of 5 elements is chosen with probability 1/10
+    let mut counts = [0; 10];
+    for _ in 0..5000{
+        let chosen = sample_fixed_size_subsets_with_rng(5, 2, 1, &mut rng);
+        counts[rank_combination(&chosen[0], 5) as usize] += 1;
+    }
+    assert!(counts.iter().all(|&c| c > 400 && c < 600));
+
+    let lm = generate_random_simplicial_complex_with_rng(&Model::LinialMeshulamM { num_vertices: 12, dimension: 2, num_faces: 40 }, &mut rng);
+    assert!(lm.contains_full_k_skeleton(1));
+    assert_eq!(lm.k_faces(2).len(), 40);
+    let pure = generate_random_simplicial_complex_with_rng(&Model::PureM { num_vertices: 12, dimension: 3, num_faces: 7, include_all_vertices: false }, &mut rng);
+    assert_eq!(pure.facets.len(), 7);
+    assert!(pure.facets.iter().all(|facet| facet.vertices.len() == 4));
+    let upper = generate_random_uniform_hypergraph_with_rng(10, &[4, 6, 2], &mut rng);
+    assert_eq!(upper.vertices.len(), 4);
+    assert_eq!(upper.hyperedges.iter().filter(|e| e.len() == 2).count(), 6);
+    assert_eq!(upper.hyperedges.iter().filter(|e| e.len() == 3).count(), 2);
+    let upper = generate_random_simplicial_complex_with_rng(&Model::UpperM { num_vertices: 10, num_faces_vec: vec![10, 0, 5] }, &mut rng);
+    assert_eq!(upper.k_faces(2).len(), 5);
+    assert_eq!(upper.k_faces(0).len(), 10);
+}
+
+#[test]
+fn test_inhomogeneous_models(){
+    assert_eq!(Kernel::Product.evaluate(&[2.0, 3.0]), 6.0);
+    assert_eq!(Kernel::Min.evaluate(&[2.0, 3.0]), 2.0);
+    assert_eq!(Kernel::Max.evaluate(&[2.0, 3.0]), 3.0);
+    assert_eq!(Kernel::Mean.evaluate(&[2.0, 3.0]), 2.5);
+
+    let mut rng = StdRng::seed_from_u64(23);
+    let full = generate_random_inhomogeneous_hypergraph_with_rng(&[1.0; 6], &[1.0, 1.0, 1.0], Kernel::Product, &mut rng);
+    assert_eq!((full.vertices.len(), full.hyperedges.len()), (6, 35));
+
+    // A hub of weight 10 and a vertex of weight 0 among 40 vertices of weight 1
+    let mut weights = vec![1.0; 42];
+    weights[0] = 10.0;
+    weights[1] = 0.0;
+    let mut hub_degree = 0;
+    let mut other_degree = 0;
+    for _ in 0..20{
+        let hg = generate_random_inhomogeneous_hypergraph_with_rng(&weights, &[1.0, 0.05], Kernel::Product, &mut rng);
+        assert!(hg.hyperedges.iter().all(|e| !e.contains(&1)));
+        hub_degree += hg.hyperedges.iter().filter(|e| e.contains(&0)).count();
+        other_degree += hg.hyperedges.iter().filter(|e| e.contains(&2)).count();
+    }
+    // Expected degrees are 0.5 * 40 = 20 and 0.05 * 40 + 0.5 = 2.5 per sample
+    assert!(hub_degree > 300 && hub_degree < 500);
+    assert!(other_degree < 100);
+
+    let sc = generate_random_simplicial_complex_with_rng(&Model::InhomogeneousLower { weights: vec![1.0, 2.0, 3.0, 4.0], prob_vec: vec![1.0, 1.0, 1.0], kernel: Kernel::Min }, &mut rng);
+    assert_eq!(sc, sc![vec![0, 1, 2], vec![0, 1, 3], vec![0, 2, 3], vec![1, 2, 3]]);
+}
+
+#[test]
+fn test_inhomogeneous_thinning_is_exact(){
+    let mut rng = StdRng::seed_from_u64(29);
+    let weights = [8.0, 4.0, 2.0, 1.0, 0.5, 0.0];
+    for kernel in [Kernel::Product, Kernel::Min, Kernel::Max, Kernel::Mean]{
+        let prob = 0.1;
+        let mut counts = [0; 20];
+        for _ in 0..2000{
+            for subset in sample_inhomogeneous_subsets_with_rng(&weights, 3, prob, kernel, &mut rng).0{
+                counts[rank_combination(&subset, 6) as usize] += 1;
+            }
+        }
+        for (rank, &count) in counts.iter().enumerate(){
+            let subset_weights: Vec<f64> = unrank_combination(rank as u128, 6, 3).iter().map(|&v| weights[v]).collect();
+            let expected = 2000.0 * (prob * kernel.evaluate(&subset_weights)).min(1.0);
+            assert!((count as f64 - expected).abs() <= 4.0 * expected.sqrt() + 1.0);
+        }
+    }
+}
+
+#[test]
+fn test_inhomogeneous_candidates_stay_close_to_kept(){
+    // Chung–Lu graphs and triangles on heavy-tailed weights, where a single bound over all hyperedges would be 1
+    let mut rng = StdRng::seed_from_u64(31);
+    let weights: Vec<f64> = (1..=2000).map(|i| (2000.0 / i as f64).powf(0.9)).collect();
+    for (size, prob) in [(2, 1.0 / 2000.0), (3, 1.0 / 2000.0f64.powi(2))]{
+        for kernel in [Kernel::Product, Kernel::Min, Kernel::Max, Kernel::Mean]{
+            let (kept, num_candidates) = sample_inhomogeneous_subsets_with_rng(&weights, size, prob, kernel, &mut rng);
+            assert!(!kept.is_empty());
+            assert!(num_candidates <= 2 * kept.len() + 20);
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use itertools::Itertools;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+use crate::sc;
+use crate::simplicial_complex::random_process::{IncrementalHomology, LinialMeshulamProcess, CliqueProcess, linial_meshulam_hitting_times_with_rng, clique_hitting_times_with_rng};
+use crate::simplicial_complex::simplicial_complex::SimplicialComplex;
+use crate::utils::utils::get_subvectors;
+
+#[test]
+fn test_incremental_homology_matches_betti_numbers(){
+    let rp2 = sc![vec![1,2,3], vec![1,3,4], vec![1,4,5], vec![1,5,6], vec![1,2,6],
+        vec![2,3,5], vec![2,4,5], vec![2,4,6], vec![3,4,6], vec![3,5,6]];
+    let mut homology = IncrementalHomology::new(2);
+    for k in 0..3{
+        for face in rp2.sorted_k_faces(k){
+            homology.add_simplex(&face);
+        }
+    }
+    assert_eq!(homology.betti_numbers(), rp2.betti_numbers());
+    assert_eq!(homology.kth_reduced_betti_number(0), 0);
+
+    // Simplices above max_dim + 1 are ignored
+    let mut truncated = IncrementalHomology::new(0);
+    for k in 0..3{
+        for face in rp2.sorted_k_faces(k){
+            truncated.add_simplex(&face);
+        }
+    }
+    assert_eq!(truncated.betti_numbers(), vec![1]);
+}
+
+#[test]
+fn test_linial_meshulam_process_tracks_homology(){
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut process = LinialMeshulamProcess::new(7, 2);
+    assert_eq!(process.reduced_betti_number(), 15);
+    assert_eq!(process.num_isolated_faces(), 21);
+    for _ in 0..20{
+        process.add_random_face_with_rng(&mut rng);
+        let betti_numbers = process.complex().betti_numbers();
+        assert_eq!(betti_numbers[1] as usize, process.reduced_betti_number());
+        assert_eq!(betti_numbers.get(2).copied().unwrap_or(0) as usize, process.top_betti_number());
+    }
+    assert_eq!(process.faces().iter().unique().count(), 20);
+
+    for _ in 0..20{
+        let times = linial_meshulam_hitting_times_with_rng(30, 1, &mut rng);
+        // An isolated vertex disconnects the graph, so it must be gone before the graph is connected
+        assert!(times.no_isolated_faces <= times.homology_vanishes);
+        assert!(times.homology_vanishes >= 29);
+        let times = linial_meshulam_hitting_times_with_rng(12, 2, &mut rng);
+        assert!(times.no_isolated_faces <= times.homology_vanishes && times.homology_vanishes <= times.num_faces);
+    }
+}
+
+#[test]
+fn test_clique_process_tracks_homology(){
+    let mut rng = StdRng::seed_from_u64(5);
+    let mut edges = get_subvectors(&(0..9).collect_vec(), 2);
+    edges.shuffle(&mut rng);
+    let mut process = CliqueProcess::new(9, 1);
+    for (i, edge) in edges.iter().enumerate(){
+        process.add_edge(edge[0], edge[1]);
+        let graph = edges[..=i].iter().map(|e| (e[0], e[1])).collect_vec();
+        let complex = SimplicialComplex::flag_complex_with_vertices(&(0..9).collect_vec(), &graph, 2);
+        assert_eq!(process.reduced_betti_number(), complex.betti_numbers().get(1).copied().unwrap_or(0));
+        let maximal_edges = complex.sorted_k_faces(1).len() - complex.k_faces(2).iter().flat_map(|t| get_subvectors(&t.vertices, 2)).unique().count();
+        assert_eq!(process.num_maximal_faces(), maximal_edges);
+    }
+    assert_eq!(process.num_edges(), 36);
+
+    let times = clique_hitting_times_with_rng(12, 1, &mut rng);
+    assert!(times.homology_vanishes <= times.num_faces && times.no_isolated_faces <= times.num_faces);
+}
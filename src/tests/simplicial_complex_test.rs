@@ -2,6 +2,7 @@ use itertools::Itertools;
 use crate::{sc, simplex};
 use crate::simplicial_complex::simplex::{Simplex, Facet};
 use crate::simplicial_complex::simplicial_complex::SimplicialComplex;
+use crate::simplicial_complex::cech::minimum_enclosing_radius;
 
 //TODO test properly homology for a large complex
 
@@ -67,4 +68,56 @@ fn test_union_and_intersection(){
     let sc2 = sc![vec![3,4,5]];
     assert_eq!(sc1.union(&sc2), sc![vec![1,2,3], vec![3,4,5]]);
     assert_eq!(sc1.intersection(&sc2), sc![vec![3]]);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_face_index(){
+    let sc = sc![vec![0,1,2], vec![2,3]];
+    let edges = sc.face_index(1);
+    assert_eq!(edges.len(), 4);
+    assert_eq!(edges.index_of(&simplex![0,1]), Some(0));
+    assert_eq!(edges.index_of(&simplex![2,3]), Some(3));
+    assert!(!edges.contains(&simplex![1,3]));
+}
+
+#[test]
+fn test_k_external_faces(){
+    let sigma: Facet = simplex![1,2,3];
+    let sc = sigma.boundary_as_complex().union(&sc![vec![3,4]]);
+    let external_triangle: Facet = simplex![1,2,3];
+    assert_eq!(sc.k_external_faces(2), vec![external_triangle]);
+    let mut external_edges: Vec<Vec<usize>> = sc.k_external_faces(1).into_iter().map(|f| f.vertices).collect();
+    external_edges.sort();
+    assert_eq!(external_edges, vec![vec![1,4], vec![2,4]]);
+}
+
+#[test]
+fn test_flag_complex(){
+    let edges = vec![(0,1), (1,2), (0,2), (2,3), (3,4), (4,5), (5,2), (0,3), (1,3)];
+    let sc = SimplicialComplex::flag_complex(&edges, 3);
+    assert_eq!(sc, sc![vec![0,1,2,3], vec![2,5], vec![3,4], vec![4,5]]);
+    assert_eq!(sc.betti_numbers(), vec![1,1,0,0]);
+    let truncated = SimplicialComplex::flag_complex(&edges, 1);
+    assert_eq!(truncated.dimension(), 1);
+    assert_eq!(truncated.facets.len(), edges.len());
+    let with_isolated = SimplicialComplex::flag_complex_with_vertices(&[0,1,2,7], &[(0,1), (1,2)], 2);
+    assert_eq!(with_isolated, sc![vec![0,1], vec![1,2], vec![7]]);
+}
+
+#[test]
+fn test_minimum_enclosing_radius(){
+    let obtuse = vec![vec![0.0, 0.0], vec![4.0, 0.0], vec![2.0, 0.5]];
+    assert!((minimum_enclosing_radius(&obtuse) - 2.0).abs() < 1e-9);
+    let equilateral = vec![vec![0.0, 0.0], vec![1.0, 0.0], vec![0.5, 3f64.sqrt() / 2.0]];
+    assert!((minimum_enclosing_radius(&equilateral) - 1.0 / 3f64.sqrt()).abs() < 1e-9);
+    assert_eq!(minimum_enclosing_radius(&[vec![1.0, 1.0], vec![1.0, 1.0]]), 0.0);
+}
+
+#[test]
+fn test_cech_complex(){
+    let equilateral = vec![vec![0.0, 0.0], vec![1.0, 0.0], vec![0.5, 3f64.sqrt() / 2.0]];
+    assert_eq!(SimplicialComplex::cech(&equilateral, 0.55, 2).betti_numbers(), vec![1,1]);
+    assert_eq!(SimplicialComplex::vietoris_rips(&equilateral, 1.1, 2), sc![vec![0,1,2]]);
+    assert_eq!(SimplicialComplex::cech(&equilateral, 0.6, 2), sc![vec![0,1,2]]);
+    assert_eq!(SimplicialComplex::cech(&equilateral, 0.4, 2).betti_numbers(), vec![3]);
+}
@@ -0,0 +1,113 @@
+use crate::simplicial_complex::ensemble::EnsembleSummary;
+use crate::simplicial_complex::random_simplicial_complex::Model;
+use crate::simplicial_complex::parameter_sweep::{ParameterSweep, SweepParameter, linspace};
+use crate::sc;
+use crate::simplicial_complex::hypothesis_test::HypothesisTest;
+use crate::simplicial_complex::simplicial_complex::SimplicialComplex;
+use crate::utils::statistics::{normal_quantile, SampleStatistics, Proportion, EmpiricalTest};
+
+#[test]
+fn test_normal_quantile(){
+    assert!((normal_quantile(0.975) - 1.959963985).abs() < 1e-8);
+    assert!((normal_quantile(0.5)).abs() < 1e-12);
+    assert!((normal_quantile(0.001) + 3.090232306).abs() < 1e-8);
+}
+
+#[test]
+fn test_sample_statistics(){
+    let stats = SampleStatistics::new(vec![4.0, 1.0, 3.0, 2.0]);
+    assert_eq!(stats.mean(), 2.5);
+    assert!((stats.variance() - 5.0 / 3.0).abs() < 1e-12);
+    assert_eq!((stats.min(), stats.max(), stats.median()), (1.0, 4.0, 2.5));
+    assert_eq!(stats.quantile(1.0 / 3.0), 2.0);
+    let (lower, upper) = stats.confidence_interval(0.95);
+    assert!(lower < 2.5 && upper > 2.5 && (upper - 2.5 - 1.959963985 * stats.standard_error()).abs() < 1e-8);
+    assert_eq!(stats.proportion(|x| x > 2.0), Proportion::new(2, 4));
+
+    let all = Proportion::new(10, 10);
+    let (lower, upper) = all.confidence_interval(0.95);
+    assert!(lower > 0.6 && lower < 1.0 && upper == 1.0);
+}
+
+#[test]
+fn test_ensemble_summary(){
+    let summary = EnsembleSummary::new(vec![vec![1], vec![1, 1], vec![2, 0, 1], vec![]]);
+    assert_eq!(summary.num_dimensions(), 3);
+    assert_eq!(summary.mean_betti_numbers(), vec![1.0, 0.25, 0.25]);
+    assert_eq!(summary.euler_characteristic_statistics().mean(), (1.0 + 0.0 + 3.0 + 0.0) / 4.0);
+    assert_eq!(summary.probability_connected(), Proportion::new(2, 4));
+    assert_eq!(summary.probability_vanishing_homology(0), Proportion::new(3, 4));
+    assert_eq!(summary.probability_vanishing_homology(1), Proportion::new(3, 4));
+    assert_eq!(summary.probability_acyclic(), Proportion::new(2, 4));
+
+    let full = EnsembleSummary::from_model_with_seed(20, Model::Clique { num_vertices: 6, prob: 1.0 }, 1);
+    assert_eq!(full.probability_connected().estimate(), 1.0);
+    assert_eq!(full.betti_statistics(0).variance(), 0.0);
+    assert_eq!(full, EnsembleSummary::from_model_with_seed(20, Model::Clique { num_vertices: 6, prob: 1.0 }, 2));
+    assert!(full.to_string().starts_with("20 samples\nb_0: mean 1.0000"));
+}
+
+#[test]
+fn test_model_with_parameter(){
+    assert_eq!(Model::Clique { num_vertices: 5, prob: 0.1 }.with_parameter(SweepParameter::Prob, 0.3), Model::Clique { num_vertices: 5, prob: 0.3 });
+    assert_eq!(Model::Lower { num_vertices: 5, prob_vec: vec![1.0, 0.5] }.with_parameter(SweepParameter::ProbVecEntry(1), 0.2),
+        Model::Lower { num_vertices: 5, prob_vec: vec![1.0, 0.2] });
+}
+
+#[test]
+#[should_panic]
+fn test_model_with_missing_parameter(){
+    Model::Clique { num_vertices: 5, prob: 0.1 }.with_parameter(SweepParameter::Radius, 0.3);
+}
+
+#[test]
+fn test_parameter_sweep_finds_connectivity_threshold(){
+    assert_eq!(linspace(0.0, 1.0, 5), vec![0.0, 0.25, 0.5, 0.75, 1.0]);
+    let template = Model::LinialMeshulam { num_vertices: 20, dimension: 1, prob: 0.0 };
+    let grid = linspace(0.02, 0.5, 13);
+    let sweep = ParameterSweep::run_with_seed(&template, SweepParameter::Prob, &grid.iter().rev().copied().collect::<Vec<f64>>(), 60, 9);
+    assert_eq!(sweep, ParameterSweep::run_with_seed(&template, SweepParameter::Prob, &grid, 60, 9));
+    assert!(sweep.parameters().windows(2).all(|w| w[0] < w[1]));
+    // The connectivity threshold of G(n, p) is around log(n) / n = 0.15
+    let critical = sweep.critical_parameter(0).unwrap();
+    assert!(critical > 0.05 && critical < 0.35);
+    assert_eq!(sweep.vanishing_probability_curve(0)[12], 1.0);
+    assert!(sweep.mean_betti_curve(0)[0] > sweep.mean_betti_curve(0)[12]);
+    assert_eq!(sweep.critical_parameter(5), None);
+}
+
+#[cfg(feature = "sc_plot")]
+#[test]
+fn test_parameter_sweep_plots(){
+    use crate::graphics::plot::{betti_mean_curves, vanishing_probability_curves};
+    let template = Model::LinialMeshulam { num_vertices: 8, dimension: 1, prob: 0.0 };
+    let sweep = ParameterSweep::run_with_seed(&template, SweepParameter::Prob, &linspace(0.1, 0.9, 3), 10, 4);
+    let means = betti_mean_curves(&sweep).to_json();
+    assert!(means.contains("\"b_0\"") && means.contains("\"b_1\""));
+    let vanishing = vanishing_probability_curves(&sweep).to_json();
+    assert!(vanishing.contains("P(b_0 = 0)"));
+}
+
+#[test]
+fn test_empirical_test(){
+    let null = SampleStatistics::new(vec![1.0, 2.0, 3.0, 4.0]);
+    let test = EmpiricalTest::new(4.0, &null);
+    assert_eq!((test.p_value_greater, test.p_value_less, test.p_value_two_sided), (0.4, 1.0, 0.8));
+    assert!((test.z_score - 1.5 / null.standard_deviation()).abs() < 1e-12);
+    let constant = SampleStatistics::new(vec![2.0; 5]);
+    assert_eq!(EmpiricalTest::new(3.0, &constant).z_score, f64::INFINITY);
+    assert_eq!(EmpiricalTest::new(2.0, &constant).z_score, 0.0);
+}
+
+#[test]
+fn test_hypothesis_test_against_null_model(){
+    let hollow_triangle = sc![vec![0,1], vec![1,2], vec![0,2]];
+    let test = HypothesisTest::run_with_seed(&hollow_triangle, Model::Clique { num_vertices: 3, prob: 1.0 }, 49, 0);
+    assert_eq!(test.betti_numbers.len(), 3);
+    assert_eq!(test.betti_numbers[1].p_value_greater, 1.0 / 50.0);
+    assert_eq!(test.betti_numbers[0].p_value_two_sided, 1.0);
+    assert_eq!(test.euler_characteristic.observed, 0.0);
+    assert_eq!(test.euler_characteristic.p_value_less, 1.0 / 50.0);
+    assert_eq!(test.significant_dimensions(0.05), vec![1]);
+    assert!(test.to_string().contains("b_1: observed 1, null mean 0.0000, z-score inf"));
+}
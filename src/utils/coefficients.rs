@@ -0,0 +1,194 @@
+use std::fmt::Debug;
+use std::ops::{Add, Mul, Neg, Sub};
+use num_integer::Integer;
+
+/// A field of coefficients for chain complexes. Betti numbers of a complex can be computed over any
+/// implementor via `SimplicialComplex::betti_numbers_over`.
+pub trait Coefficient: Copy + Clone + PartialEq + Debug + Send + Sync + 'static
+    + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Neg<Output = Self> {
+    fn zero() -> Self;
+    fn one() -> Self;
+    /// The image of an integer under the canonical map Z -> F.
+    fn from_i32(n: i32) -> Self;
+    /// The multiplicative inverse, panics on zero.
+    fn inverse(&self) -> Self;
+    fn characteristic() -> u32;
+
+    fn is_zero(&self) -> bool {
+        *self == Self::zero()
+    }
+}
+
+/// Whether n is prime, by trial division. A const fn so that `Fp<P>` can check P at compile time.
+pub(crate) const fn is_prime(n: u32) -> bool {
+    if n < 2 {
+        return false
+    }
+    let mut d: u64 = 2;
+    while d * d <= n as u64 {
+        if n as u64 % d == 0 {
+            return false
+        }
+        d += 1;
+    }
+    true
+}
+
+/// The finite field F_p = Z/pZ. Using `Fp<P>` with a P that is not prime fails to compile, as inverses would not
+/// be well defined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fp<const P: u32>(u32);
+
+impl<const P: u32> Fp<P> {
+    /// Evaluated wherever an element of F_p is constructed, so a composite P is a compile time error.
+    const PRIME: () = assert!(is_prime(P), "Fp<P> requires P to be prime.");
+
+    pub fn new(n: i64) -> Self {
+        let () = Self::PRIME;
+        Self(n.rem_euclid(P as i64) as u32)
+    }
+
+    pub fn value(&self) -> u32 {
+        self.0
+    }
+}
+
+impl<const P: u32> Add for Fp<P> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Self(((self.0 as u64 + other.0 as u64) % P as u64) as u32)
+    }
+}
+
+impl<const P: u32> Sub for Fp<P> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Self(((self.0 as u64 + P as u64 - other.0 as u64) % P as u64) as u32)
+    }
+}
+
+impl<const P: u32> Mul for Fp<P> {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        Self(((self.0 as u64 * other.0 as u64) % P as u64) as u32)
+    }
+}
+
+impl<const P: u32> Neg for Fp<P> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::zero() - self
+    }
+}
+
+impl<const P: u32> Coefficient for Fp<P> {
+    fn zero() -> Self {
+        let () = Self::PRIME;
+        Self(0)
+    }
+
+    fn one() -> Self {
+        let () = Self::PRIME;
+        Self(1)
+    }
+
+    fn from_i32(n: i32) -> Self {
+        Self::new(n as i64)
+    }
+
+    /// Computed by Fermat's little theorem, a^{-1} = a^{p-2}.
+    fn inverse(&self) -> Self {
+        let () = Self::PRIME;
+        assert!(self.0 != 0, "Zero has no multiplicative inverse in F_{}.", P);
+        let mut result = Self::one();
+        let mut base = *self;
+        let mut exp = P - 2;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            exp >>= 1;
+        }
+        result
+    }
+
+    fn characteristic() -> u32 {
+        P
+    }
+}
+
+/// The rationals Q, stored as a reduced fraction with positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128
+}
+
+impl Rational {
+    pub fn new(num: i128, den: i128) -> Self {
+        assert!(den != 0, "Rational with zero denominator.");
+        let g = num.gcd(&den);
+        let sign = if den < 0 { -1 } else { 1 };
+        Self { num: sign * num / g, den: sign * den / g }
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.num
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.den
+    }
+}
+
+impl Add for Rational {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Self::new(self.num * other.den + other.num * self.den, self.den * other.den)
+    }
+}
+
+impl Sub for Rational {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.num * other.den - other.num * self.den, self.den * other.den)
+    }
+}
+
+impl Mul for Rational {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        Self::new(self.num * other.num, self.den * other.den)
+    }
+}
+
+impl Neg for Rational {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self { num: -self.num, den: self.den }
+    }
+}
+
+impl Coefficient for Rational {
+    fn zero() -> Self {
+        Self { num: 0, den: 1 }
+    }
+
+    fn one() -> Self {
+        Self { num: 1, den: 1 }
+    }
+
+    fn from_i32(n: i32) -> Self {
+        Self { num: n as i128, den: 1 }
+    }
+
+    fn inverse(&self) -> Self {
+        assert!(self.num != 0, "Zero has no multiplicative inverse in Q.");
+        Self::new(self.den, self.num)
+    }
+
+    fn characteristic() -> u32 {
+        0
+    }
+}
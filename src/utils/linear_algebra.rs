@@ -1,7 +1,12 @@
-use std::ops::AddAssign;
+use std::collections::HashMap;
+use std::ops::{AddAssign, SubAssign};
 use nalgebra::DMatrix;
 
+use crate::utils::coefficients::Coefficient;
+
 pub fn gaussian_elimination(mut matrix: DMatrix<i32>) -> DMatrix<i32>{
+    // Boundary matrices carry signed entries, over Z/2 we only care about their parity
+    matrix.iter_mut().for_each(|n| *n = n.rem_euclid(2));
     for x in 0..matrix.nrows().max(matrix.ncols()){
         // if there exists some i/geq x and j\geq x s.t. a[i,j] = 1 then swap rows x and i and swap cols x and j
         match (x+1..matrix.nrows()).flat_map(|i| (x..matrix.ncols()).map(move |j| (i, j))).find(|&(i, j)| matrix[(i, j)] == 1) {
@@ -51,4 +56,268 @@ pub fn row_nullity_smith_normal_matrix(smith_normal_matrix: &DMatrix<i32>) -> i3
 
 pub fn columns_nullity(smith_normal_matrix: &DMatrix<i32>) -> i32{
     i32::try_from(smith_normal_matrix.ncols()).unwrap() - rank_smith_normal_matrix(smith_normal_matrix)
-}
\ No newline at end of file
+}
+
+/// Reduces an integer matrix to its Smith normal form over Z and returns the non-zero diagonal entries
+/// d_1 | d_2 | ... | d_r (the invariant factors), all taken to be positive. The number of returned entries is the rank of
+/// the matrix over Z (equivalently over Q).
+///
+/// Row and column operations are performed with i64 arithmetic, pivoting on the entry of smallest absolute value
+/// to keep coefficient growth down.
+pub fn integer_smith_normal_form(matrix: &DMatrix<i32>) -> Vec<i64>{
+    let mut m: DMatrix<i64> = matrix.map(|n| n as i64);
+    let (nrows, ncols) = (m.nrows(), m.ncols());
+    let mut invariant_factors: Vec<i64> = Vec::new();
+    for t in 0..nrows.min(ncols){
+        loop {
+            // Move the smallest non-zero entry of the remaining submatrix into the pivot position
+            let pivot = (t..nrows).flat_map(|i| (t..ncols).map(move |j| (i, j)))
+                .filter(|&(i, j)| m[(i, j)] != 0)
+                .min_by_key(|&(i, j)| m[(i, j)].abs());
+            match pivot {
+                Some((i, j)) => {
+                    m.swap_rows(t, i);
+                    m.swap_columns(t, j);
+                },
+                None => return invariant_factors,
+            };
+            let p = m[(t, t)];
+            let mut remainder = false;
+            for i in t+1..nrows{
+                let q = m[(i, t)] / p;
+                if q != 0 {
+                    let row_t = m.row(t) * q;
+                    m.row_mut(i).sub_assign(row_t);
+                }
+                remainder |= m[(i, t)] != 0;
+            }
+            for j in t+1..ncols{
+                let q = m[(t, j)] / p;
+                if q != 0 {
+                    let col_t = m.column(t) * q;
+                    m.column_mut(j).sub_assign(col_t);
+                }
+                remainder |= m[(t, j)] != 0;
+            }
+            if remainder {
+                continue
+            }
+            // The pivot must divide every remaining entry, otherwise fold the offending row into row t and go again
+            match (t+1..nrows).find(|&i| (t+1..ncols).any(|j| m[(i, j)] % p != 0)) {
+                Some(i) => {
+                    let row_i = m.row(i).clone_owned();
+                    m.row_mut(t).add_assign(row_i);
+                },
+                None => break,
+            }
+        }
+        invariant_factors.push(m[(t, t)].abs());
+    }
+    invariant_factors
+}
+
+/// Reduces a matrix over the field F to row echelon form in place, returning the pivot columns. If `reduced` is set the
+/// pivots are scaled to one and cleared above as well as below, giving the reduced row echelon form.
+pub fn row_echelon_form<F: Coefficient>(m: &mut DMatrix<F>, reduced: bool) -> Vec<usize>{
+    let (nrows, ncols) = (m.nrows(), m.ncols());
+    let mut pivot_columns: Vec<usize> = Vec::new();
+    for j in 0..ncols{
+        let rank = pivot_columns.len();
+        if rank == nrows {
+            break
+        }
+        let pivot = match (rank..nrows).find(|&i| !m[(i, j)].is_zero()) {
+            Some(i) => i,
+            None => continue,
+        };
+        m.swap_rows(rank, pivot);
+        let inv = m[(rank, j)].inverse();
+        if reduced {
+            for l in j..ncols{
+                m[(rank, l)] = m[(rank, l)] * inv;
+            }
+        }
+        for i in 0..nrows{
+            if i == rank || (i < rank && !reduced) || m[(i, j)].is_zero() {
+                continue
+            }
+            let factor = if reduced { m[(i, j)] } else { m[(i, j)] * inv };
+            for l in j..ncols{
+                let x = m[(rank, l)];
+                m[(i, l)] = m[(i, l)] - factor * x;
+            }
+        }
+        pivot_columns.push(j);
+    }
+    pivot_columns
+}
+
+/// Computes the rank of an integer matrix after mapping its entries into the coefficient field F, by
+/// reducing it to row echelon form with Gaussian elimination over F.
+pub fn rank_over<F: Coefficient>(matrix: &DMatrix<i32>) -> i32{
+    let mut m: DMatrix<F> = matrix.map(F::from_i32);
+    row_echelon_form(&mut m, false).len() as i32
+}
+
+/// Returns a basis for the (right) kernel of an integer matrix over the field F, read off from the free columns of
+/// its reduced row echelon form.
+pub fn kernel_basis_over<F: Coefficient>(matrix: &DMatrix<i32>) -> Vec<Vec<F>>{
+    let mut m: DMatrix<F> = matrix.map(F::from_i32);
+    let pivot_columns = row_echelon_form(&mut m, true);
+    (0..m.ncols()).filter(|j| !pivot_columns.contains(j)).map(|free| {
+        let mut v: Vec<F> = vec![F::zero(); m.ncols()];
+        v[free] = F::one();
+        for (row, &pivot) in pivot_columns.iter().enumerate(){
+            v[pivot] = -m[(row, free)];
+        }
+        v
+    }).collect()
+}
+
+/// Stacks the columns of `image` followed by the given vectors into a single matrix over F.
+fn augment<F: Coefficient>(image: &DMatrix<F>, vectors: &[&[F]]) -> DMatrix<F>{
+    let num_image = image.ncols();
+    let mut m: DMatrix<F> = DMatrix::from_element(image.nrows(), num_image + vectors.len(), F::zero());
+    m.columns_mut(0, num_image).copy_from(image);
+    for (j, v) in vectors.iter().enumerate(){
+        for (i, &c) in v.iter().enumerate(){
+            m[(i, num_image + j)] = c;
+        }
+    }
+    m
+}
+
+/// Given a matrix whose columns span a subspace W and a list of candidate vectors, returns the indices of the candidates
+/// that are linearly independent modulo W, chosen greedily in order. When the candidates span a space containing W,
+/// these give a basis of the quotient, e.g. cycles modulo boundaries.
+pub fn independent_modulo<F: Coefficient>(image: &DMatrix<F>, candidates: &[Vec<F>]) -> Vec<usize>{
+    let mut m = augment(image, &candidates.iter().map(|v| v.as_slice()).collect::<Vec<&[F]>>());
+    row_echelon_form(&mut m, false).into_iter()
+        .filter(|&j| j >= image.ncols())
+        .map(|j| j - image.ncols())
+        .collect()
+}
+
+/// Expresses v as a combination of `basis` modulo the column span of `image`, returning the coefficients of the basis
+/// vectors. The basis vectors are assumed to be independent modulo the image, as returned by `independent_modulo`.
+/// Returns None if v does not lie in the span.
+pub fn coordinates_modulo<F: Coefficient>(image: &DMatrix<F>, basis: &[Vec<F>], v: &[F]) -> Option<Vec<F>>{
+    let mut vectors: Vec<&[F]> = basis.iter().map(|b| b.as_slice()).collect();
+    vectors.push(v);
+    let mut m = augment(image, &vectors);
+    let pivot_columns = row_echelon_form(&mut m, true);
+    let target = image.ncols() + basis.len();
+    if pivot_columns.contains(&target) {
+        return None
+    }
+    Some((0..basis.len()).map(|j| {
+        match pivot_columns.iter().position(|&col| col == image.ncols() + j) {
+            Some(row) => m[(row, target)],
+            None => F::zero()
+        }
+    }).collect())
+}
+
+/// A matrix over Z/2 stored column by column, each column being the sorted list of row indices of its non-zero entries.
+/// A boundary matrix of k-faces has only k+1 non-zero entries per column, so this is far smaller than the dense form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseBinaryMatrix {
+    pub nrows: usize,
+    pub columns: Vec<Vec<usize>>
+}
+
+impl SparseBinaryMatrix {
+    pub fn new(nrows: usize, columns: Vec<Vec<usize>>) -> Self {
+        Self { nrows, columns }
+    }
+
+    pub fn ncols(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// The dense matrix with the same entries.
+    pub fn to_dense(&self) -> DMatrix<i32> {
+        let mut m = DMatrix::from_element(self.nrows, self.ncols(), 0);
+        for (j, column) in self.columns.iter().enumerate(){
+            for &i in column{
+                m[(i, j)] = 1;
+            }
+        }
+        m
+    }
+}
+
+/// Adds two sparse columns over Z/2, i.e. takes the symmetric difference of their sorted row indices.
+pub fn add_columns_mod_2(a: &[usize], b: &[usize]) -> Vec<usize>{
+    let mut result: Vec<usize> = Vec::with_capacity(a.len() + b.len());
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => { result.push(a[i]); i += 1; },
+            std::cmp::Ordering::Greater => { result.push(b[j]); j += 1; },
+            std::cmp::Ordering::Equal => { i += 1; j += 1; }
+        }
+    }
+    result.extend_from_slice(&a[i..]);
+    result.extend_from_slice(&b[j..]);
+    result
+}
+
+/// Reduces a sparse matrix over Z/2 by left to right column additions, until no two non-zero columns share the same
+/// lowest non-zero row (their pivot). The non-zero columns of the result are linearly independent, so their number
+/// is the rank of the matrix.
+pub fn sparse_column_reduction(mut matrix: SparseBinaryMatrix) -> SparseBinaryMatrix{
+    // Maps a pivot row to the reduced column that has it as its lowest entry
+    let mut pivots: HashMap<usize, usize> = HashMap::new();
+    for j in 0..matrix.ncols(){
+        while let Some(&low) = matrix.columns[j].last() {
+            match pivots.get(&low) {
+                Some(&k) => matrix.columns[j] = add_columns_mod_2(&matrix.columns[j], &matrix.columns[k]),
+                None => {
+                    pivots.insert(low, j);
+                    break
+                }
+            }
+        }
+    }
+    matrix
+}
+
+/// The rank over Z/2 of a sparse matrix, computed by column reduction.
+pub fn sparse_rank_mod_2(matrix: SparseBinaryMatrix) -> i32{
+    sparse_column_reduction(matrix).columns.iter().filter(|column| !column.is_empty()).count() as i32
+}
+
+/// The column reduction of `sparse_column_reduction` performed one column at a time, for matrices that grow by
+/// appending columns. Only the reduced columns with a pivot are kept, as the columns reducing to zero never take part
+/// in later reductions.
+#[derive(Default)]
+pub struct IncrementalColumnReduction {
+    pivots: HashMap<usize, Vec<usize>>
+}
+
+impl IncrementalColumnReduction {
+    pub fn new() -> Self {
+        Self { pivots: HashMap::new() }
+    }
+
+    /// The rank over Z/2 of the columns added so far.
+    pub fn rank(&self) -> usize {
+        self.pivots.len()
+    }
+
+    /// Reduces a column, given by its sorted non-zero rows, against those added before it. Returns true if it is
+    /// independent of them (so the rank grows by one) and false if it reduces to zero.
+    pub fn add_column(&mut self, mut column: Vec<usize>) -> bool {
+        while let Some(&low) = column.last() {
+            match self.pivots.get(&low) {
+                Some(reduced) => column = add_columns_mod_2(&column, reduced),
+                None => {
+                    self.pivots.insert(low, column);
+                    return true
+                }
+            }
+        }
+        false
+    }
+}
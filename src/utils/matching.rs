@@ -0,0 +1,128 @@
+use std::collections::VecDeque;
+use nalgebra::DMatrix;
+
+/// A minimum cost perfect matching of a square cost matrix by the Hungarian algorithm with potentials, in O(n^3).
+/// Returns the column assigned to each row.
+pub fn hungarian(cost: &DMatrix<f64>) -> Vec<usize> {
+    assert_eq!(cost.nrows(), cost.ncols(), "The Hungarian algorithm needs a square cost matrix.");
+    let n = cost.nrows();
+    // Potentials u (rows) and v (columns), and the row matched to each column, all 1-indexed with 0 as a sentinel
+    let mut u = vec![0.0; n + 1];
+    let mut v = vec![0.0; n + 1];
+    let mut row_of = vec![0; n + 1];
+    let mut way = vec![0; n + 1];
+    for i in 1..=n {
+        row_of[0] = i;
+        let mut j0 = 0;
+        let mut min_slack = vec![f64::INFINITY; n + 1];
+        let mut used = vec![false; n + 1];
+        loop {
+            used[j0] = true;
+            let i0 = row_of[j0];
+            let mut delta = f64::INFINITY;
+            let mut j1 = 0;
+            for j in 1..=n {
+                if !used[j] {
+                    let slack = cost[(i0 - 1, j - 1)] - u[i0] - v[j];
+                    if slack < min_slack[j] {
+                        min_slack[j] = slack;
+                        way[j] = j0;
+                    }
+                    if min_slack[j] < delta {
+                        delta = min_slack[j];
+                        j1 = j;
+                    }
+                }
+            }
+            for j in 0..=n {
+                if used[j] {
+                    u[row_of[j]] += delta;
+                    v[j] -= delta;
+                }
+                else {
+                    min_slack[j] -= delta;
+                }
+            }
+            j0 = j1;
+            if row_of[j0] == 0 {
+                break
+            }
+        }
+        // Flip the augmenting path back to the root
+        loop {
+            let j1 = way[j0];
+            row_of[j0] = row_of[j1];
+            j0 = j1;
+            if j0 == 0 {
+                break
+            }
+        }
+    }
+    let mut assignment = vec![0; n];
+    for j in 1..=n {
+        if row_of[j] != 0 {
+            assignment[row_of[j] - 1] = j - 1;
+        }
+    }
+    assignment
+}
+
+/// The size of a maximum matching of a bipartite graph by Hopcroft–Karp, in O(E sqrt(V)). adjacency[i] lists the right
+/// vertices, in 0..num_right, joined to left vertex i.
+pub fn hopcroft_karp(num_right: usize, adjacency: &[Vec<usize>]) -> usize {
+    let num_left = adjacency.len();
+    const FREE: usize = usize::MAX;
+    let mut left_match = vec![FREE; num_left];
+    let mut right_match = vec![FREE; num_right];
+    let mut layer = vec![0usize; num_left];
+    let mut matching = 0;
+    loop {
+        // Breadth first search from the free left vertices, layering the graph by alternating path length
+        let mut queue: VecDeque<usize> = VecDeque::new();
+        for i in 0..num_left {
+            if left_match[i] == FREE {
+                layer[i] = 0;
+                queue.push_back(i);
+            }
+            else {
+                layer[i] = usize::MAX;
+            }
+        }
+        let mut found = false;
+        while let Some(i) = queue.pop_front() {
+            for &j in adjacency[i].iter() {
+                match right_match[j] {
+                    FREE => found = true,
+                    k if layer[k] == usize::MAX => {
+                        layer[k] = layer[i] + 1;
+                        queue.push_back(k);
+                    },
+                    _ => ()
+                }
+            }
+        }
+        if !found {
+            return matching
+        }
+        for i in 0..num_left {
+            if left_match[i] == FREE && augment(i, adjacency, &mut left_match, &mut right_match, &mut layer) {
+                matching += 1;
+            }
+        }
+    }
+}
+
+/// Depth first search for an augmenting path from left vertex i along the layers of the breadth first search.
+fn augment(i: usize, adjacency: &[Vec<usize>], left_match: &mut [usize], right_match: &mut [usize], layer: &mut [usize]) -> bool {
+    for &j in adjacency[i].iter() {
+        let k = right_match[j];
+        if k == usize::MAX || (layer[k] == layer[i] + 1 && augment(k, adjacency, left_match, right_match, layer)) {
+            left_match[i] = j;
+            right_match[j] = i;
+            return true
+        }
+    }
+    // Dead end, so no later search needs to try it
+    layer[i] = usize::MAX;
+    false
+}
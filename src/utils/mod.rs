@@ -1,2 +1,6 @@
+#[allow(clippy::module_inception)]
 pub mod utils;
-pub mod linear_algebra;
\ No newline at end of file
+pub mod linear_algebra;
+pub mod coefficients;
+pub mod statistics;
+pub mod matching;
\ No newline at end of file
@@ -0,0 +1,192 @@
+/// The quantile function of the standard normal distribution, by Acklam's rational approximation (relative error
+/// below 1.2e-9). Panics unless 0 < p < 1.
+// The coefficients are quoted as published
+#[allow(clippy::excessive_precision)]
+pub fn normal_quantile(p: f64) -> f64 {
+    assert!(p > 0.0 && p < 1.0, "The normal quantile is only defined for 0 < p < 1, got {}.", p);
+    const A: [f64; 6] = [-3.969683028665376e+01, 2.209460984245205e+02, -2.759285104469687e+02, 1.383577518672690e+02, -3.066479806614716e+01, 2.506628277459239e+00];
+    const B: [f64; 5] = [-5.447609879822406e+01, 1.615858368580409e+02, -1.556989798598866e+02, 6.680131188771972e+01, -1.328068155288572e+01];
+    const C: [f64; 6] = [-7.784894002430293e-03, -3.223964580411365e-01, -2.400758277161838e+00, -2.549732539343734e+00, 4.374664141464968e+00, 2.938163982698783e+00];
+    const D: [f64; 4] = [7.784695709041462e-03, 3.224671290700398e-01, 2.445134137142996e+00, 3.754408661907416e+00];
+    let tail = |q: f64| (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5]) / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0);
+    if p < 0.02425 {
+        tail((-2.0 * p.ln()).sqrt())
+    }
+    else if p > 1.0 - 0.02425 {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    }
+    else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
+
+/// Summary statistics of a sample of real numbers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SampleStatistics {
+    values: Vec<f64>
+}
+
+impl SampleStatistics {
+    /// Panics if the sample is empty or contains NaN.
+    pub fn new(mut values: Vec<f64>) -> Self {
+        assert!(!values.is_empty(), "Cannot summarise an empty sample.");
+        values.sort_by(|a, b| a.partial_cmp(b).expect("The sample contains NaN."));
+        Self { values }
+    }
+
+    pub fn from_integers(values: &[i32]) -> Self {
+        Self::new(values.iter().map(|&x| x as f64).collect())
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.values.iter().sum::<f64>() / self.len() as f64
+    }
+
+    /// The unbiased sample variance, which is 0 for a sample of size one.
+    pub fn variance(&self) -> f64 {
+        if self.len() < 2 {
+            return 0.0
+        }
+        let mean = self.mean();
+        self.values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (self.len() - 1) as f64
+    }
+
+    pub fn standard_deviation(&self) -> f64 {
+        self.variance().sqrt()
+    }
+
+    /// The standard error of the mean.
+    pub fn standard_error(&self) -> f64 {
+        (self.variance() / self.len() as f64).sqrt()
+    }
+
+    pub fn min(&self) -> f64 {
+        self.values[0]
+    }
+
+    pub fn max(&self) -> f64 {
+        self.values[self.len() - 1]
+    }
+
+    /// The empirical q-quantile, interpolating linearly between order statistics. Panics unless 0 <= q <= 1.
+    pub fn quantile(&self, q: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&q), "Quantiles are only defined for 0 <= q <= 1, got {}.", q);
+        let position = q * (self.len() - 1) as f64;
+        let (lower, upper) = (position.floor() as usize, position.ceil() as usize);
+        self.values[lower] + (position - lower as f64) * (self.values[upper] - self.values[lower])
+    }
+
+    pub fn median(&self) -> f64 {
+        self.quantile(0.5)
+    }
+
+    /// The normal approximation confidence interval for the mean at the given level, e.g. 0.95.
+    pub fn confidence_interval(&self, level: f64) -> (f64, f64) {
+        let half_width = normal_quantile(0.5 + level / 2.0) * self.standard_error();
+        (self.mean() - half_width, self.mean() + half_width)
+    }
+
+    /// The fraction of the sample satisfying the predicate.
+    pub fn proportion(&self, predicate: impl Fn(f64) -> bool) -> Proportion {
+        Proportion::new(self.values.iter().filter(|&&x| predicate(x)).count(), self.len())
+    }
+}
+
+/// An empirical probability, the number of successes out of a number of independent trials.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Proportion {
+    pub successes: usize,
+    pub trials: usize
+}
+
+impl Proportion {
+    pub fn new(successes: usize, trials: usize) -> Self {
+        assert!(successes <= trials && trials > 0, "Need 0 <= successes <= trials and at least one trial.");
+        Self { successes, trials }
+    }
+
+    pub fn estimate(&self) -> f64 {
+        self.successes as f64 / self.trials as f64
+    }
+
+    /// The Wilson score interval at the given level, e.g. 0.95. Unlike the normal approximation it stays inside [0, 1]
+    /// and is not degenerate when every trial succeeds or every trial fails, which is common near thresholds.
+    pub fn confidence_interval(&self, level: f64) -> (f64, f64) {
+        let z = normal_quantile(0.5 + level / 2.0);
+        let n = self.trials as f64;
+        let p = self.estimate();
+        let denominator = 1.0 + z * z / n;
+        let centre = (p + z * z / (2.0 * n)) / denominator;
+        let half_width = z * (p * (1.0 - p) / n + z * z / (4.0 * n * n)).sqrt() / denominator;
+        ((centre - half_width).max(0.0), (centre + half_width).min(1.0))
+    }
+}
+
+/// Where an observed value falls in a simulated null distribution.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EmpiricalTest {
+    pub observed: f64,
+    pub null_mean: f64,
+    pub null_standard_deviation: f64,
+    /// (observed - null mean) / null standard deviation. Infinite if the null sample is constant and differs from
+    /// the observed value, and 0 if it is constant and equal to it.
+    pub z_score: f64,
+    /// The empirical probability of a null value at least as large as the observed one.
+    pub p_value_greater: f64,
+    /// The empirical probability of a null value at least as small as the observed one.
+    pub p_value_less: f64,
+    /// Twice the smaller one-sided p-value, capped at 1.
+    pub p_value_two_sided: f64
+}
+
+impl EmpiricalTest {
+    /// Compares the observed value with the null sample. The p-values count the observed value as one of the samples,
+    /// (1 + #{null values as extreme}) / (1 + number of null values), so they are never 0 and are valid for a finite
+    /// number of simulations.
+    pub fn new(observed: f64, null: &SampleStatistics) -> Self {
+        let (null_mean, null_standard_deviation) = (null.mean(), null.standard_deviation());
+        let z_score = if null_standard_deviation > 0.0 {
+            (observed - null_mean) / null_standard_deviation
+        }
+        else if observed == null_mean {
+            0.0
+        }
+        else {
+            (observed - null_mean).signum() * f64::INFINITY
+        };
+        let p_value = |extreme: Proportion| (1 + extreme.successes) as f64 / (1 + extreme.trials) as f64;
+        let p_value_greater = p_value(null.proportion(|x| x >= observed));
+        let p_value_less = p_value(null.proportion(|x| x <= observed));
+        Self {
+            observed,
+            null_mean,
+            null_standard_deviation,
+            z_score,
+            p_value_greater,
+            p_value_less,
+            p_value_two_sided: (2.0 * p_value_greater.min(p_value_less)).min(1.0)
+        }
+    }
+}
+
+/// The error function, by the rational approximation 7.1.26 of Abramowitz and Stegun (absolute error below 1.5e-7).
+pub fn erf(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.3275911 * x.abs());
+    let polynomial = t * (0.254829592 + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    (1.0 - polynomial * (-x * x).exp()).copysign(x)
+}
+
+/// The distribution function of the normal distribution with the given mean and standard deviation.
+pub fn normal_cdf(x: f64, mean: f64, standard_deviation: f64) -> f64 {
+    0.5 * (1.0 + erf((x - mean) / (standard_deviation * std::f64::consts::SQRT_2)))
+}
@@ -3,12 +3,13 @@ use std::collections::HashMap;
 
 use rand::{Rng, thread_rng};
 use itertools::Itertools;
+use num_integer::binomial;
 use rayon::prelude::*;
 
-pub fn alternating_sum(v: &Vec<i32>) -> i32{
+pub fn alternating_sum(v: &[i32]) -> i32{
     let mut running_sum: i32 = 0;
-    for i in 0..v.len(){
-        running_sum += (-1 as i32).pow(i as u32) * v[i] as i32;
+    for (i, x) in v.iter().enumerate(){
+        running_sum += (-1i32).pow(i as u32) * x;
     }
     running_sum
 }
@@ -43,13 +44,13 @@ pub fn filter_maximal_sets(mut s: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
 /// i.e, if s = [[1], [1,2], [1,2,3], [4,5]] the output would be [[1,2,3], [4,5]]
 pub fn par_filter_maximal_sets(s: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
     let mut subvecs_by_length = _subsets_by_length(s);
-    let n = subvecs_by_length.keys().max().unwrap().clone();
+    let n = *subvecs_by_length.keys().max().unwrap();
     let mut result: Vec<Vec<usize>> = subvecs_by_length.remove(&n).unwrap();
     for i in (1..n).rev(){
         match subvecs_by_length.get_mut(&i) {
             Some(subvecs) => {
                 let mut filtered_subsets: Vec<Vec<usize>> = subvecs.par_iter()
-                                                            .filter(|w| result.iter().any(|v| !is_subvector(v, *w, w.len())))
+                                                            .filter(|w| result.iter().any(|v| !is_subvector(v, w, w.len())))
                                                             .cloned()
                                                             .collect();
                 result.append(&mut filtered_subsets);
@@ -78,11 +79,11 @@ pub fn par_filter_downward_closed_sets(mut s: Vec<Vec<usize>>) -> Vec<Vec<usize>
     }
     let mut subsets_by_length: HashMap<usize, Vec<Vec<usize>>> = _subsets_by_length(s);
     let mut result: Vec<Vec<usize>> = subsets_by_length.remove(&1).unwrap();
-    let n = subsets_by_length.keys().max().unwrap().clone();
+    let n = *subsets_by_length.keys().max().unwrap();
     for i in 2..=n{
         let subsets = subsets_by_length.get_mut(&i).unwrap();
         let mut filtered_subsets: Vec<Vec<usize>> = subsets.par_iter()
-                                                            .filter(|face| get_subvectors(*face, i-1).iter().all(|vec| result.contains(vec)))
+                                                            .filter(|face| get_subvectors(face, i-1).iter().all(|vec| result.contains(vec)))
                                                             .cloned()
                                                             .collect();
         result.append(&mut filtered_subsets);
@@ -100,7 +101,7 @@ pub fn filter_downward_closed_sets(mut s: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
             subset.remove(elem);
             subset
         }).collect::<Vec<HashSet<usize>>>().iter().all(|subset| {
-            result.contains(&subset)});
+            result.contains(subset)});
         if boundary_present {
             result.push(set);
         }
@@ -108,28 +109,159 @@ pub fn filter_downward_closed_sets(mut s: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
     filter_maximal_sets(result.into_iter().map(|set| set.into_iter().collect()).collect::<Vec<Vec<usize>>>())
 }
 
-pub fn randomly_select_items_from_vec<T: Clone>(v: &Vec<T>, p: f64) -> Vec<T> {
+pub fn randomly_select_items_from_vec<T: Clone>(v: &[T], p: f64) -> Vec<T> {
+    randomly_select_items_from_vec_with_rng(v, p, &mut thread_rng())
+}
+
+/// Keep each item independently with probability p, drawing from the given random number generator.
+pub fn randomly_select_items_from_vec_with_rng<T: Clone, R: Rng + ?Sized>(v: &[T], p: f64, rng: &mut R) -> Vec<T> {
     if p == 0.0 {
         return Vec::new()
     }
     else if p == 1.0 {
-        return v.clone()
+        return v.to_vec()
     }
-    let mut rng = thread_rng();
     v.iter()
         .filter(|_| rng.gen_bool(p))
         .cloned()
         .collect()
 }
 
+/// Derives the seed of the ith independent stream from a master seed (via the SplitMix64 finaliser), so that parallel
+/// work can be given its own generator that does not depend on which thread runs it or in what order.
+pub fn derive_seed(seed: u64, index: u64) -> u64 {
+    let mut z = seed.wrapping_add(index.wrapping_add(1).wrapping_mul(0x9E3779B97F4A7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+/// The k-subset of {0, ..., n-1} with the given rank in lexicographic order, as a sorted vector. This is the inverse of
+/// enumerating `get_subvectors(&(0..n).collect::<Vec<usize>>(), k)` and taking the element at position rank.
+pub fn unrank_combination(mut rank: u128, n: usize, k: usize) -> Vec<usize> {
+    assert!(rank < binomial(n as u128, k as u128), "Rank {} is out of range for {}-subsets of {} elements.", rank, k, n);
+    let mut combination: Vec<usize> = Vec::with_capacity(k);
+    let mut next: usize = 0;
+    for i in 0..k {
+        loop {
+            // Number of subsets whose ith element is next
+            let count = binomial((n - next - 1) as u128, (k - i - 1) as u128);
+            if rank < count {
+                break
+            }
+            rank -= count;
+            next += 1;
+        }
+        combination.push(next);
+        next += 1;
+    }
+    combination
+}
+
+/// The rank of a sorted subset of {0, ..., n-1} among the subsets of the same size in lexicographic order, the inverse
+/// of `unrank_combination`.
+pub fn rank_combination(combination: &[usize], n: usize) -> u128 {
+    let k = combination.len();
+    let mut rank: u128 = 0;
+    let mut next: usize = 0;
+    for (i, &c) in combination.iter().enumerate() {
+        // Count the subsets agreeing before position i with a smaller ith element
+        for skipped in next..c {
+            rank += binomial((n - skipped - 1) as u128, (k - i - 1) as u128);
+        }
+        next = c + 1;
+    }
+    rank
+}
+
+/// Keep each k-subset of {0, ..., n-1} independently with probability p, returned in lexicographic order.
+///
+/// Rather than listing all C(n, k) subsets and flipping a coin for each, the gaps between the ranks of consecutive kept
+/// subsets are drawn from the geometric distribution and the kept ranks unranked. The cost is proportional to the number
+/// of subsets kept, rather than the number of subsets.
+pub fn sample_subsets_with_rng<R: Rng + ?Sized>(n: usize, k: usize, p: f64, rng: &mut R) -> Vec<Vec<usize>> {
+    if p <= 0.0 {
+        return Vec::new()
+    }
+    else if p >= 1.0 {
+        return get_subvectors(&(0..n).collect::<Vec<usize>>(), k)
+    }
+    sample_ranks_with_rng(binomial(n as u128, k as u128), p, rng).into_iter().map(|rank| unrank_combination(rank, n, k)).collect()
+}
+
+/// Keep each of the ranks 0, ..., total-1 independently with probability p, returned in increasing order. The gaps
+/// between kept ranks are drawn from the geometric distribution, so the cost is proportional to the number kept.
+pub fn sample_ranks_with_rng<R: Rng + ?Sized>(total: u128, p: f64, rng: &mut R) -> Vec<u128> {
+    if p <= 0.0 {
+        return Vec::new()
+    }
+    else if p >= 1.0 {
+        return (0..total).collect()
+    }
+    let log_q = (1.0 - p).ln();
+    let mut ranks: Vec<u128> = Vec::new();
+    let mut rank: u128 = 0;
+    loop {
+        let u: f64 = rng.gen();
+        let skip = ((1.0 - u).ln() / log_q).floor();
+        if skip >= (total - rank) as f64 {
+            break
+        }
+        rank += skip as u128;
+        if rank >= total {
+            break
+        }
+        ranks.push(rank);
+        rank += 1;
+    }
+    ranks
+}
+
+/// A uniformly random collection of exactly m distinct k-subsets of {0, ..., n-1}, returned in lexicographic order.
+/// Panics if there are fewer than m such subsets.
+///
+/// The ranks are drawn with Floyd's algorithm, which needs only m draws and never lists the C(n, k) subsets.
+pub fn sample_fixed_size_subsets_with_rng<R: Rng + ?Sized>(n: usize, k: usize, m: usize, rng: &mut R) -> Vec<Vec<usize>> {
+    let total = binomial(n as u128, k as u128);
+    assert!(m as u128 <= total, "Cannot choose {} distinct {}-subsets of {} elements.", m, k, n);
+    let mut ranks: HashSet<u128> = HashSet::with_capacity(m);
+    for j in (total - m as u128)..total {
+        let t = rng.gen_range(0..=j);
+        if !ranks.insert(t) {
+            ranks.insert(j);
+        }
+    }
+    let mut ranks: Vec<u128> = ranks.into_iter().collect();
+    ranks.sort();
+    ranks.into_iter().map(|rank| unrank_combination(rank, n, k)).collect()
+}
+
+/// Whether every element of the sorted slice a is in the sorted slice b.
+pub fn is_sorted_subset(a: &[usize], b: &[usize]) -> bool {
+    if a.len() > b.len() {
+        return false
+    }
+    let mut b_iter = b.iter();
+    a.iter().all(|x| b_iter.any(|y| y == x))
+}
+
+/// n evenly spaced values from start to end inclusive, for use as a grid of parameters or times.
+pub fn linspace(start: f64, end: f64, n: usize) -> Vec<f64> {
+    match n {
+        0 => Vec::new(),
+        1 => vec![start],
+        _ => (0..n).map(|i| start + (end - start) * i as f64 / (n - 1) as f64).collect()
+    }
+}
+
 /// Consume a vector and return a vector containing all subvectors of length k, preserving ordering
-pub fn get_subvectors(v: &Vec<usize>, k: usize) -> Vec<Vec<usize>> {
-    v.into_iter().combinations(k)
+pub fn get_subvectors(v: &[usize], k: usize) -> Vec<Vec<usize>> {
+    v.iter().combinations(k)
     .map(|subvec| subvec.into_iter().cloned().collect())
     .collect()
 }
 
-fn is_subvector(v: &Vec<usize>, w: &Vec<usize>, k: usize) -> bool{
+fn is_subvector(v: &[usize], w: &Vec<usize>, k: usize) -> bool{
     if v.len() <= w.len(){
         return false
     }
@@ -140,4 +272,46 @@ fn is_subvector(v: &Vec<usize>, w: &Vec<usize>, k: usize) -> bool{
 pub fn remove_element<T: Clone>(vec: &mut Vec<T>, index: usize) -> Vec<T>{
     vec.remove(index);
     vec.clone()
-}
\ No newline at end of file
+}
+
+/// Returns the maximal cliques of the graph with the given vertices and edges, each with its vertices sorted, using the
+/// Bron–Kerbosch algorithm with pivoting. Isolated vertices are maximal cliques of size one.
+pub fn maximal_cliques(vertices: &[usize], edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+    let mut adjacency: HashMap<usize, HashSet<usize>> = vertices.iter().map(|&v| (v, HashSet::new())).collect();
+    for &(u, v) in edges{
+        if u == v {
+            continue
+        }
+        adjacency.entry(u).or_default().insert(v);
+        adjacency.entry(v).or_default().insert(u);
+    }
+    let mut cliques: Vec<Vec<usize>> = Vec::new();
+    let candidates: HashSet<usize> = adjacency.keys().copied().collect();
+    _bron_kerbosch(&adjacency, &mut Vec::new(), candidates, HashSet::new(), &mut cliques);
+    for clique in cliques.iter_mut(){
+        clique.sort();
+    }
+    cliques
+}
+
+fn _bron_kerbosch(adjacency: &HashMap<usize, HashSet<usize>>, clique: &mut Vec<usize>, mut candidates: HashSet<usize>,
+    mut excluded: HashSet<usize>, cliques: &mut Vec<Vec<usize>>) {
+    if candidates.is_empty() {
+        if excluded.is_empty() {
+            cliques.push(clique.clone());
+        }
+        return
+    }
+    // Pivot on the vertex with the most candidate neighbours, its neighbours need not be branched on
+    let pivot = candidates.union(&excluded).max_by_key(|&u| adjacency[u].intersection(&candidates).count()).copied().unwrap();
+    let branch: Vec<usize> = candidates.difference(&adjacency[&pivot]).copied().collect();
+    for v in branch{
+        let neighbours = &adjacency[&v];
+        clique.push(v);
+        _bron_kerbosch(adjacency, clique, candidates.intersection(neighbours).copied().collect(),
+            excluded.intersection(neighbours).copied().collect(), cliques);
+        clique.pop();
+        candidates.remove(&v);
+        excluded.insert(v);
+    }
+}